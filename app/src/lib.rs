@@ -1,7 +1,9 @@
 mod plugin;
 mod runner;
 
-use ecs::prelude::{IntoSystemSet, Resource, ScheduleLabel, World};
+use ecs::prelude::{
+    ErrorHandler, Event, IntoCondition, IntoSystemSet, Resource, ScheduleLabel, States, World,
+};
 pub use plugin::*;
 use runner::Runner;
 use std::collections::HashSet;
@@ -59,11 +61,42 @@ impl App {
         self
     }
 
-    pub fn add_systems<M, L>(&mut self, _label: L, sys_set: impl IntoSystemSet<M>) -> &mut Self
-    where
-        L: ScheduleLabel,
-    {
-        self.world.add_systems::<M, L>(sys_set);
+    /// Add the [`Events`](ecs::prelude::Events) resource for events of type `T`.
+    pub fn add_event<T: Event>(&mut self) -> &mut Self {
+        self.world.add_event::<T>();
+        self
+    }
+
+    pub fn add_systems<M>(
+        &mut self,
+        label: impl ScheduleLabel,
+        sys_set: impl IntoSystemSet<M>,
+    ) -> &mut Self {
+        self.world.add_systems(label, sys_set);
+        self
+    }
+
+    /// Only run the schedule of the label when the condition is true, see
+    /// [`World::add_schedule_condition`].
+    pub fn add_schedule_condition<M>(
+        &mut self,
+        label: impl ScheduleLabel,
+        condition: impl IntoCondition<M>,
+    ) -> &mut Self {
+        self.world.add_schedule_condition(label, condition);
+        self
+    }
+
+    /// Add the [`State`](ecs::prelude::State) and [`NextState`](ecs::prelude::NextState)
+    /// resources for the states `S`, see [`World::add_state`].
+    pub fn add_state<S: States + Default>(&mut self) -> &mut Self {
+        self.world.add_state::<S>();
+        self
+    }
+
+    /// Set what is done with the errors of systems, see [`World::set_error_handler`].
+    pub fn set_error_handler(&mut self, handler: ErrorHandler) -> &mut Self {
+        self.world.set_error_handler(handler);
         self
     }
 
@@ -45,3 +45,10 @@ where
         (self)(world)
     }
 }
+
+/// Adds the [`Time`](ecs::prelude::Time) resources and the [`FixedUpdate`](ecs::prelude::FixedUpdate)
+/// schedule, see [`World::init_time`].
+#[allow(non_snake_case)]
+pub fn TimePlugin(app: &mut App) {
+    app.world().init_time();
+}
@@ -6,7 +6,7 @@ impl<F: FnOnce(World) -> World + 'static> Runner for F {}
 
 pub fn simple_runner() -> Box<dyn Runner> {
     Box::new(move |mut world: World| {
-        world.run_startup_labels();
+        world.run_startup_schedules();
 
         loop {
             world.update();
@@ -20,7 +20,7 @@ pub fn simple_runner_with_stop_condition(
     stop_condition: impl Fn(&World) -> bool + 'static,
 ) -> Box<dyn Runner> {
     Box::new(move |mut world: World| {
-        world.run_startup_labels();
+        world.run_startup_schedules();
 
         loop {
             world.update();
@@ -1,9 +1,13 @@
 use quote::quote;
-use syn::DeriveInput;
+use syn::{DeriveInput, LitStr};
 
 pub fn derive(input: DeriveInput) -> proc_macro2::TokenStream {
     let name = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let storage_type = match storage_type(&input) {
+        Ok(storage_type) => storage_type,
+        Err(err) => return err.to_compile_error(),
+    };
 
     let expanded = quote! {
         impl #impl_generics Component for #name #ty_generics #where_clause {
@@ -26,6 +30,14 @@ pub fn derive(input: DeriveInput) -> proc_macro2::TokenStream {
             fn as_any(&self) -> &dyn std::any::Any {
                 self
             }
+
+            fn storage_type(&self) -> StorageType {
+                <Self as Component>::static_storage_type()
+            }
+
+            fn static_storage_type() -> StorageType {
+                #storage_type
+            }
         }
     }
     .into();
@@ -33,8 +45,38 @@ pub fn derive(input: DeriveInput) -> proc_macro2::TokenStream {
     expanded
 }
 
+/// Parse the `#[component(storage = "...")]` attribute, defaults to `Table`.
+fn storage_type(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let mut storage_type = quote! { StorageType::Table };
+    for attr in input.attrs.iter() {
+        if !attr.path().is_ident("component") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("storage") {
+                return Err(meta.error("unknown component attribute, expected `storage`"));
+            }
+            let value: LitStr = meta.value()?.parse()?;
+            storage_type = match value.value().as_str() {
+                "Table" => quote! { StorageType::Table },
+                "SparseSet" => quote! { StorageType::SparseSet },
+                _ => {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "expected `\"Table\"` or `\"SparseSet\"`",
+                    ))
+                }
+            };
+            Ok(())
+        })?;
+    }
+    Ok(storage_type)
+}
+
 // fn as_any(&self) -> &dyn std::any::Any;
 // fn comp_id(&self) -> ComponentId;
 // fn layout(&self) -> std::alloc::Layout;
 // fn drop_fn(&self) -> Option<unsafe fn(OwningPtr<'_>)>;
 // fn name(&self) -> &'static str;
+// fn storage_type(&self) -> StorageType;
+// fn static_storage_type() -> StorageType;
@@ -1,2 +1,4 @@
 pub(crate) mod component;
 pub(crate) mod resource;
+pub(crate) mod schedule_label;
+pub(crate) mod states;
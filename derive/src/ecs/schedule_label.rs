@@ -0,0 +1,31 @@
+use quote::quote;
+use syn::DeriveInput;
+
+pub fn derive(input: DeriveInput) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ScheduleLabel for #name #ty_generics #where_clause {
+            fn dyn_clone(&self) -> Box<dyn ScheduleLabel> {
+                Box::new(std::clone::Clone::clone(self))
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+
+            fn dyn_eq(&self, other: &dyn ScheduleLabel) -> bool {
+                other
+                    .as_any()
+                    .downcast_ref::<Self>()
+                    .is_some_and(|other| self == other)
+            }
+
+            fn dyn_hash(&self, mut state: &mut dyn std::hash::Hasher) {
+                std::hash::Hash::hash(&std::any::TypeId::of::<Self>(), &mut state);
+                std::hash::Hash::hash(self, &mut state);
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use quote::quote;
+use syn::DeriveInput;
+
+pub fn derive(input: DeriveInput) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics States for #name #ty_generics #where_clause {}
+    }
+}
@@ -2,7 +2,7 @@ mod ecs;
 
 use syn::{parse_macro_input, DeriveInput};
 
-#[proc_macro_derive(Component)]
+#[proc_macro_derive(Component, attributes(component))]
 pub fn derive_component(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     ecs::component::derive(input).into()
@@ -13,3 +13,15 @@ pub fn derive_resource(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
     let input = parse_macro_input!(input as DeriveInput);
     ecs::resource::derive(input).into()
 }
+
+#[proc_macro_derive(ScheduleLabel)]
+pub fn derive_schedule_label(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    ecs::schedule_label::derive(input).into()
+}
+
+#[proc_macro_derive(States)]
+pub fn derive_states(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    ecs::states::derive(input).into()
+}
@@ -0,0 +1,196 @@
+mod column;
+mod table;
+
+pub use column::Column;
+pub use table::{Table, TableId, Tables};
+
+use bevy_utils::HashMap;
+
+use crate::{
+    component::{ComponentDesc, ComponentId, StorageType},
+    entity::Entity,
+};
+
+/// Identifies an [`Archetype`] in the [`World`](crate::prelude::World). Ids are never reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ArchetypeId(usize);
+
+impl ArchetypeId {
+    /// The archetype of entities without any components.
+    pub const EMPTY: ArchetypeId = ArchetypeId(0);
+
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Where an [`Entity`] and its components are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntityLocation {
+    pub archetype_id: ArchetypeId,
+    /// The index of the entity in [`Archetype::entities`].
+    pub archetype_row: usize,
+    pub table_id: TableId,
+    /// The row of the entity in all the columns of the table.
+    pub table_row: usize,
+}
+
+impl EntityLocation {
+    pub(crate) const INVALID: EntityLocation = EntityLocation {
+        archetype_id: ArchetypeId(usize::MAX),
+        archetype_row: usize::MAX,
+        table_id: TableId::EMPTY,
+        table_row: usize::MAX,
+    };
+}
+
+/// Groups all the entities that have the exact same set of components, regardless of how the
+/// components are stored. The [`Table`] components of the entities are stored in the table of the
+/// archetype, and the [`SparseSet`](StorageType::SparseSet) components in the world's sparse sets.
+pub struct Archetype {
+    id: ArchetypeId,
+    table_id: TableId,
+    /// Sorted, used as the key of the archetype.
+    component_ids: Vec<ComponentId>,
+    entities: Vec<Entity>,
+    /// The table row of each entity in `entities`.
+    table_rows: Vec<usize>,
+}
+
+impl Archetype {
+    fn new(id: ArchetypeId, table_id: TableId, component_ids: Vec<ComponentId>) -> Self {
+        Self {
+            id,
+            table_id,
+            component_ids,
+            entities: Vec::new(),
+            table_rows: Vec::new(),
+        }
+    }
+
+    pub fn id(&self) -> ArchetypeId {
+        self.id
+    }
+
+    /// The table that stores the table components of the entities in this archetype.
+    pub fn table_id(&self) -> TableId {
+        self.table_id
+    }
+
+    /// The (sorted) ids of the components of the entities in this archetype.
+    pub fn component_ids(&self) -> &[ComponentId] {
+        &self.component_ids
+    }
+
+    pub fn contains(&self, comp_id: ComponentId) -> bool {
+        self.component_ids.binary_search(&comp_id).is_ok()
+    }
+
+    /// The entities in this archetype.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// The table rows of the entities, in the same order as [`Archetype::entities`].
+    pub fn table_rows(&self) -> &[usize] {
+        &self.table_rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Add an entity to the archetype, and return its archetype row.
+    pub(crate) fn push_entity(&mut self, entity: Entity, table_row: usize) -> usize {
+        self.entities.push(entity);
+        self.table_rows.push(table_row);
+        self.entities.len() - 1
+    }
+
+    /// Swap remove the entity at `row`, and return the entity that was moved into its place (if any).
+    pub(crate) fn swap_remove_entity(&mut self, row: usize) -> Option<Entity> {
+        self.entities.swap_remove(row);
+        self.table_rows.swap_remove(row);
+        self.entities.get(row).copied()
+    }
+
+    /// Update the table row of the entity at `row`, after it was moved inside the table.
+    pub(crate) fn set_table_row(&mut self, row: usize, table_row: usize) {
+        self.table_rows[row] = table_row;
+    }
+}
+
+/// All the archetypes of the world, indexed by their [`ArchetypeId`] and by their set of components.
+pub struct Archetypes {
+    archetypes: Vec<Archetype>,
+    by_components: HashMap<Vec<ComponentId>, ArchetypeId>,
+}
+
+impl Default for Archetypes {
+    fn default() -> Self {
+        let mut by_components = HashMap::new();
+        by_components.insert(Vec::new(), ArchetypeId::EMPTY);
+        Self {
+            archetypes: vec![Archetype::new(
+                ArchetypeId::EMPTY,
+                TableId::EMPTY,
+                Vec::new(),
+            )],
+            by_components,
+        }
+    }
+}
+
+impl Archetypes {
+    pub fn get(&self, id: ArchetypeId) -> &Archetype {
+        &self.archetypes[id.0]
+    }
+
+    pub(crate) fn get_mut(&mut self, id: ArchetypeId) -> &mut Archetype {
+        &mut self.archetypes[id.0]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Archetype> {
+        self.archetypes.iter()
+    }
+
+    /// The amount of archetypes.
+    pub fn len(&self) -> usize {
+        self.archetypes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.archetypes.is_empty()
+    }
+
+    /// Get the archetype with exactly the given set of components, or create it (and its table)
+    /// if it doesn't exist. `descs` must contain the description of every component in the set.
+    pub(crate) fn get_or_insert(
+        &mut self,
+        mut component_ids: Vec<ComponentId>,
+        descs: &HashMap<ComponentId, ComponentDesc>,
+        tables: &mut Tables,
+    ) -> ArchetypeId {
+        component_ids.sort();
+        component_ids.dedup();
+        if let Some(&id) = self.by_components.get(&component_ids) {
+            return id;
+        }
+
+        let table_components = component_ids
+            .iter()
+            .copied()
+            .filter(|comp_id| descs[comp_id].storage_type() == StorageType::Table)
+            .collect();
+        let table_id = tables.get_or_insert(table_components, descs);
+        let id = ArchetypeId(self.archetypes.len());
+        self.by_components.insert(component_ids.clone(), id);
+        self.archetypes
+            .push(Archetype::new(id, table_id, component_ids));
+        id
+    }
+}
@@ -0,0 +1,107 @@
+use std::cell::UnsafeCell;
+
+use bevy_ptr::{OwningPtr, Ptr, PtrMut};
+
+use crate::{
+    change_detection::{ComponentTicks, Tick},
+    component::ComponentDesc,
+    utils::BlobVec,
+};
+
+/// A single component's data in an [`Archetype`](super::Archetype), one row per entity.
+pub struct Column {
+    data: BlobVec,
+    /// When the component in each row was added and last changed.
+    ticks: Vec<UnsafeCell<ComponentTicks>>,
+}
+
+impl Column {
+    pub(crate) fn new(desc: &ComponentDesc) -> Self {
+        Self {
+            // SAFETY: The drop function of the description is valid for the components it describes.
+            data: unsafe { BlobVec::new(desc.layout(), desc.drop_fn(), 0) },
+            ticks: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Clamp the ticks of every row, see [`Tick::check_tick`].
+    pub(crate) fn check_change_ticks(&mut self, this_run: Tick) {
+        for ticks in self.ticks.iter_mut() {
+            ticks.get_mut().check_ticks(this_run);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// # Safety
+    /// `value` must point to a valid value of the component stored in this [`Column`].
+    pub(crate) unsafe fn push(&mut self, value: OwningPtr<'_>, ticks: ComponentTicks) {
+        self.data.push(value);
+        self.ticks.push(UnsafeCell::new(ticks));
+    }
+
+    /// Replace (and drop) the component at `row`, and mark it as changed.
+    /// # Safety
+    /// `row` must be in bounds, and `value` must point to a valid value of the component
+    /// stored in this [`Column`].
+    pub(crate) unsafe fn replace(&mut self, row: usize, value: OwningPtr<'_>, change_tick: Tick) {
+        self.data.replace_unchecked(row, value);
+        self.ticks[row].get_mut().changed = change_tick;
+    }
+
+    /// Get (type erased) shared access to the component at `row`.
+    pub fn get(&self, row: usize) -> Option<Ptr<'_>> {
+        // SAFETY: We checked that the row is in bounds.
+        (row < self.len()).then(|| unsafe { self.data.get_unchecked(row) })
+    }
+
+    /// Get (type erased) exclusive access to the component at `row`.
+    pub fn get_mut(&mut self, row: usize) -> Option<PtrMut<'_>> {
+        // SAFETY: We checked that the row is in bounds.
+        (row < self.len()).then(|| unsafe { self.data.get_unchecked_mut(row) })
+    }
+
+    /// Get the whole column as a slice of `T`.
+    /// # Safety
+    /// `T` must be the type of the components stored in this [`Column`].
+    pub unsafe fn get_slice<T>(&self) -> &[UnsafeCell<T>] {
+        self.data.get_slice()
+    }
+
+    /// Get the ticks of the component at `row`.
+    pub fn get_ticks(&self, row: usize) -> Option<ComponentTicks> {
+        // SAFETY: Shared access to the column means nobody is changing the ticks.
+        self.ticks.get(row).map(|ticks| unsafe { *ticks.get() })
+    }
+
+    /// Get the ticks of the whole column, one per row.
+    pub fn get_ticks_slice(&self) -> &[UnsafeCell<ComponentTicks>] {
+        &self.ticks
+    }
+
+    /// Swap remove the component at `row` and drop it.
+    /// # Safety
+    /// `row` must be in bounds.
+    pub(crate) unsafe fn swap_remove_and_drop(&mut self, row: usize) {
+        self.data.swap_remove_and_drop_unchecked(row);
+        self.ticks.swap_remove(row);
+    }
+
+    /// Swap remove the component at `row` without dropping it, and return it with its ticks.
+    /// The returned pointer is only valid until the next time a component is pushed into this [`Column`].
+    /// # Safety
+    /// `row` must be in bounds.
+    pub(crate) unsafe fn swap_remove_and_forget(
+        &mut self,
+        row: usize,
+    ) -> (OwningPtr<'_>, ComponentTicks) {
+        let ticks = self.ticks.swap_remove(row).into_inner();
+        (self.data.swap_remove_and_forget_unchecked(row), ticks)
+    }
+}
@@ -0,0 +1,177 @@
+use bevy_utils::HashMap;
+
+use super::Column;
+use crate::{
+    change_detection::Tick,
+    component::{ComponentDesc, ComponentId},
+    entity::Entity,
+};
+
+/// Identifies a [`Table`] in the [`World`](crate::prelude::World). Ids are never reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TableId(usize);
+
+impl TableId {
+    /// The table of entities without any table components.
+    pub const EMPTY: TableId = TableId(0);
+
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Stores the [`Table`](crate::prelude::StorageType::Table) components of entities, each
+/// component in its own [`Column`], and the components of an entity share the same row.
+/// Archetypes that differ only in their sparse-set components share the same table.
+pub struct Table {
+    id: TableId,
+    /// Sorted, used as the key of the table.
+    component_ids: Vec<ComponentId>,
+    columns: HashMap<ComponentId, Column>,
+    entities: Vec<Entity>,
+}
+
+impl Table {
+    fn new(
+        id: TableId,
+        component_ids: Vec<ComponentId>,
+        columns: HashMap<ComponentId, Column>,
+    ) -> Self {
+        Self {
+            id,
+            component_ids,
+            columns,
+            entities: Vec::new(),
+        }
+    }
+
+    pub fn id(&self) -> TableId {
+        self.id
+    }
+
+    /// The (sorted) ids of the components stored in this table.
+    pub fn component_ids(&self) -> &[ComponentId] {
+        &self.component_ids
+    }
+
+    pub fn contains(&self, comp_id: ComponentId) -> bool {
+        self.columns.contains_key(&comp_id)
+    }
+
+    /// The entities in this table, ordered by their row.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn column(&self, comp_id: ComponentId) -> Option<&Column> {
+        self.columns.get(&comp_id)
+    }
+
+    pub(crate) fn column_mut(&mut self, comp_id: ComponentId) -> Option<&mut Column> {
+        self.columns.get_mut(&comp_id)
+    }
+
+    /// Add an entity to the table, and return its row.
+    /// The caller is responsible for pushing its components to all the columns.
+    pub(crate) fn push_entity(&mut self, entity: Entity) -> usize {
+        self.entities.push(entity);
+        self.entities.len() - 1
+    }
+
+    /// Swap remove the entity at `row`, and return the entity that was moved into its place (if any).
+    /// The caller is responsible for removing its components from all the columns.
+    pub(crate) fn swap_remove_entity(&mut self, row: usize) -> Option<Entity> {
+        self.entities.swap_remove(row);
+        self.entities.get(row).copied()
+    }
+}
+
+/// All the tables of the world, indexed by their [`TableId`] and by their set of components.
+pub struct Tables {
+    tables: Vec<Table>,
+    by_components: HashMap<Vec<ComponentId>, TableId>,
+}
+
+impl Default for Tables {
+    fn default() -> Self {
+        let mut by_components = HashMap::new();
+        by_components.insert(Vec::new(), TableId::EMPTY);
+        Self {
+            tables: vec![Table::new(TableId::EMPTY, Vec::new(), HashMap::new())],
+            by_components,
+        }
+    }
+}
+
+impl Tables {
+    pub fn get(&self, id: TableId) -> &Table {
+        &self.tables[id.0]
+    }
+
+    pub(crate) fn get_mut(&mut self, id: TableId) -> &mut Table {
+        &mut self.tables[id.0]
+    }
+
+    /// Get exclusive access to two different tables at once.
+    pub(crate) fn get_2_mut(&mut self, a: TableId, b: TableId) -> (&mut Table, &mut Table) {
+        assert_ne!(a, b, "Can't borrow the same table twice");
+        if a.0 < b.0 {
+            let (left, right) = self.tables.split_at_mut(b.0);
+            (&mut left[a.0], &mut right[0])
+        } else {
+            let (left, right) = self.tables.split_at_mut(a.0);
+            (&mut right[0], &mut left[b.0])
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Table> {
+        self.tables.iter()
+    }
+
+    /// Clamp the ticks of every component of every table, see [`Tick::check_tick`].
+    pub(crate) fn check_change_ticks(&mut self, this_run: Tick) {
+        for table in self.tables.iter_mut() {
+            for column in table.columns.values_mut() {
+                column.check_change_ticks(this_run);
+            }
+        }
+    }
+
+    /// The amount of tables.
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Get the table with exactly the given (sorted) set of components, or create it if it doesn't exist.
+    /// `descs` must contain the description of every component in the set.
+    pub(crate) fn get_or_insert(
+        &mut self,
+        component_ids: Vec<ComponentId>,
+        descs: &HashMap<ComponentId, ComponentDesc>,
+    ) -> TableId {
+        if let Some(&id) = self.by_components.get(&component_ids) {
+            return id;
+        }
+
+        let id = TableId(self.tables.len());
+        let columns = component_ids
+            .iter()
+            .map(|comp_id| (*comp_id, Column::new(&descs[comp_id])))
+            .collect();
+        self.by_components.insert(component_ids.clone(), id);
+        self.tables.push(Table::new(id, component_ids, columns));
+        id
+    }
+}
@@ -0,0 +1,208 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// How many ticks can pass before [`World::update`](crate::prelude::World::update) clamps the ticks
+/// of the world, see [`World::check_change_ticks`](crate::prelude::World::check_change_ticks).
+pub const CHECK_TICK_THRESHOLD: u32 = 518_400_000;
+
+/// The maximum age of a tick, older ticks are clamped to it. Far enough from [`u32::MAX`] that a
+/// clamped tick doesn't wrap around before the next check.
+pub const MAX_CHANGE_AGE: u32 = u32::MAX - (2 * CHECK_TICK_THRESHOLD - 1);
+
+/// A point in time of the [`World`](crate::prelude::World), used to detect changes.
+/// The world's change tick is incremented every time a system runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Tick(u32);
+
+impl Tick {
+    pub const fn new(tick: u32) -> Self {
+        Self(tick)
+    }
+
+    pub fn get(self) -> u32 {
+        self.0
+    }
+
+    /// Check if this tick happened after `last_run`, as seen from `this_run`.
+    /// The comparison is done relative to `this_run` so it survives the tick wrapping around.
+    pub fn is_newer_than(self, last_run: Tick, this_run: Tick) -> bool {
+        let ticks_since_self = this_run.0.wrapping_sub(self.0);
+        let ticks_since_last_run = this_run.0.wrapping_sub(last_run.0);
+        ticks_since_last_run > ticks_since_self
+    }
+
+    /// Clamp the tick if it's more than [`MAX_CHANGE_AGE`] ticks older than `this_run`, so it
+    /// doesn't look newer once the ticks wrap around. Returns `true` if it was clamped.
+    pub fn check_tick(&mut self, this_run: Tick) -> bool {
+        if this_run.0.wrapping_sub(self.0) > MAX_CHANGE_AGE {
+            self.0 = this_run.0.wrapping_sub(MAX_CHANGE_AGE);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// When a component was added, and when it was last changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComponentTicks {
+    pub added: Tick,
+    pub changed: Tick,
+}
+
+impl ComponentTicks {
+    pub fn new(change_tick: Tick) -> Self {
+        Self {
+            added: change_tick,
+            changed: change_tick,
+        }
+    }
+
+    /// Clamp both ticks, see [`Tick::check_tick`].
+    pub fn check_ticks(&mut self, this_run: Tick) {
+        self.added.check_tick(this_run);
+        self.changed.check_tick(this_run);
+    }
+}
+
+/// Same as [`ComponentTicks`] but for resources, which can be changed through a shared reference to the world.
+#[derive(Debug)]
+pub(crate) struct ResourceTicks {
+    added: AtomicU32,
+    changed: AtomicU32,
+}
+
+impl ResourceTicks {
+    pub(crate) fn new(change_tick: Tick) -> Self {
+        Self {
+            added: AtomicU32::new(change_tick.0),
+            changed: AtomicU32::new(change_tick.0),
+        }
+    }
+
+    pub(crate) fn added(&self) -> Tick {
+        Tick(self.added.load(Ordering::Acquire))
+    }
+
+    pub(crate) fn changed(&self) -> Tick {
+        Tick(self.changed.load(Ordering::Acquire))
+    }
+
+    pub(crate) fn set_changed(&self, change_tick: Tick) {
+        self.changed.store(change_tick.0, Ordering::Release);
+    }
+
+    /// Clamp both ticks, see [`Tick::check_tick`].
+    pub(crate) fn check_ticks(&self, this_run: Tick) {
+        for tick in [&self.added, &self.changed] {
+            let mut checked = Tick(tick.load(Ordering::Acquire));
+            if checked.check_tick(this_run) {
+                tick.store(checked.0, Ordering::Release);
+            }
+        }
+    }
+}
+
+/// Shared access to a component, that also tells if the component was added or changed
+/// since the last time the system (or [`World::update`](crate::prelude::World::update)) ran.
+pub struct Ref<'w, T: ?Sized> {
+    pub(crate) value: &'w T,
+    pub(crate) ticks: &'w ComponentTicks,
+    pub(crate) last_run: Tick,
+    pub(crate) this_run: Tick,
+}
+
+impl<'w, T: ?Sized> Ref<'w, T> {
+    pub fn is_added(&self) -> bool {
+        self.ticks.added.is_newer_than(self.last_run, self.this_run)
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.ticks
+            .changed
+            .is_newer_than(self.last_run, self.this_run)
+    }
+
+    pub fn last_changed(&self) -> Tick {
+        self.ticks.changed
+    }
+
+    pub fn into_inner(self) -> &'w T {
+        self.value
+    }
+}
+
+impl<'w, T: ?Sized> Deref for Ref<'w, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
+
+impl<'w, T: ?Sized + std::fmt::Debug> std::fmt::Debug for Ref<'w, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Ref").field(&self.value).finish()
+    }
+}
+
+/// Exclusive access to a component, that marks the component as changed when it's mutably dereferenced.
+pub struct Mut<'w, T: ?Sized> {
+    pub(crate) value: &'w mut T,
+    pub(crate) ticks: &'w mut ComponentTicks,
+    pub(crate) last_run: Tick,
+    pub(crate) this_run: Tick,
+}
+
+impl<'w, T: ?Sized> Mut<'w, T> {
+    pub fn is_added(&self) -> bool {
+        self.ticks.added.is_newer_than(self.last_run, self.this_run)
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.ticks
+            .changed
+            .is_newer_than(self.last_run, self.this_run)
+    }
+
+    pub fn last_changed(&self) -> Tick {
+        self.ticks.changed
+    }
+
+    /// Mark the component as changed, without mutating it.
+    pub fn set_changed(&mut self) {
+        self.ticks.changed = self.this_run;
+    }
+
+    /// Get exclusive access to the component without marking it as changed.
+    pub fn bypass_change_detection(&mut self) -> &mut T {
+        self.value
+    }
+
+    /// Mark the component as changed, and get exclusive access to it for the rest of `'w`.
+    pub fn into_inner(mut self) -> &'w mut T {
+        self.set_changed();
+        self.value
+    }
+}
+
+impl<'w, T: ?Sized> Deref for Mut<'w, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
+
+impl<'w, T: ?Sized> DerefMut for Mut<'w, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.set_changed();
+        self.value
+    }
+}
+
+impl<'w, T: ?Sized + std::fmt::Debug> std::fmt::Debug for Mut<'w, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Mut").field(&self.value).finish()
+    }
+}
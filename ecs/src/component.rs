@@ -13,6 +13,24 @@ pub trait Component: 'static + Send + Sync {
     fn layout(&self) -> std::alloc::Layout;
     fn drop_fn(&self) -> Option<unsafe fn(OwningPtr<'_>)>;
     fn name(&self) -> &'static str;
+    /// Where components of this type are stored, see [`StorageType`].
+    fn storage_type(&self) -> StorageType;
+    /// Same as [`Component::storage_type`], without needing an instance of the component.
+    fn static_storage_type() -> StorageType
+    where
+        Self: Sized;
+}
+
+/// How the components of a certain type are stored in the [`World`](crate::prelude::World).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StorageType {
+    /// Stored in the columns of the entity's table, fast to iterate but slow to add and remove.
+    /// Use it for components that are mostly iterated, like positions.
+    #[default]
+    Table,
+    /// Stored in a sparse set per component, slower to iterate but fast to add and remove.
+    /// Use it for components that are often added and removed, like marker components.
+    SparseSet,
 }
 
 pub type ComponentId = std::any::TypeId;
@@ -21,11 +39,13 @@ pub fn comp_id<C: Component>() -> ComponentId {
     std::any::TypeId::of::<C>()
 }
 
+#[derive(Clone, Debug)]
 pub struct ComponentDesc {
     _name: &'static str,
     _id: ComponentId,
     layout: std::alloc::Layout,
     drop: Option<unsafe fn(bevy_ptr::OwningPtr<'_>)>,
+    storage_type: StorageType,
 }
 
 pub(crate) fn _downcast_res<C: Component>(comp: &dyn Component) -> Option<&C> {
@@ -43,6 +63,7 @@ impl ComponentDesc {
             _id: std::any::TypeId::of::<C>(),
             layout: std::alloc::Layout::new::<C>(),
             drop: Some(drop_ptr::<C>),
+            storage_type: C::static_storage_type(),
         }
     }
 
@@ -51,24 +72,57 @@ impl ComponentDesc {
         layout: Layout,
         name: &'static str,
         drop: Option<unsafe fn(OwningPtr<'_>)>,
+        storage_type: StorageType,
     ) -> Self {
         Self {
             _name: name,
             _id: comp_id,
             layout,
             drop,
+            storage_type,
         }
     }
+
+    pub fn storage_type(&self) -> StorageType {
+        self.storage_type
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    pub fn drop_fn(&self) -> Option<unsafe fn(OwningPtr<'_>)> {
+        self.drop
+    }
 }
 
 pub trait Bundle {
     fn components(self) -> Vec<Box<dyn Component>>;
+
+    /// The ids of the components in the bundle, in the same order as [`Bundle::components`].
+    fn component_ids() -> Vec<ComponentId>;
+
+    /// Build the bundle back from its components, `take` is called once per id in
+    /// [`Bundle::component_ids`] (in the same order).
+    ///
+    /// # Safety
+    /// `take` must return a pointer to a valid value of the component with the requested id,
+    /// and the value must not be used (or dropped) again by the caller.
+    unsafe fn from_components<'a>(take: &mut dyn FnMut(ComponentId) -> OwningPtr<'a>) -> Self;
 }
 
 impl<C: Component> Bundle for C {
     fn components(self) -> Vec<Box<dyn Component>> {
         vec![Box::new(self)]
     }
+
+    fn component_ids() -> Vec<ComponentId> {
+        vec![comp_id::<C>()]
+    }
+
+    unsafe fn from_components<'a>(take: &mut dyn FnMut(ComponentId) -> OwningPtr<'a>) -> Self {
+        take(comp_id::<C>()).read::<C>()
+    }
 }
 
 macro_rules! impl_bundle {
@@ -81,6 +135,18 @@ macro_rules! impl_bundle {
                 $(cmps.extend($name.components());)*
                 cmps
             }
+
+            #[allow(unused_mut)]
+            fn component_ids() -> Vec<ComponentId> {
+                let mut ids = Vec::new();
+                $(ids.extend($name::component_ids());)*
+                ids
+            }
+
+            #[allow(unused_variables, clippy::unused_unit)]
+            unsafe fn from_components<'a>(take: &mut dyn FnMut(ComponentId) -> OwningPtr<'a>) -> Self {
+                ($($name::from_components(take),)*)
+            }
         }
     };
 }
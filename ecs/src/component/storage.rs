@@ -1,15 +1,25 @@
-use crate::{entity::Entity, utils::BlobVec};
+use crate::{
+    change_detection::{ComponentTicks, Tick},
+    entity::Entity,
+    utils::BlobVec,
+};
 use bevy_ptr::{OwningPtr, Ptr, PtrMut};
 use hashbrown::HashMap;
+use std::cell::UnsafeCell;
 
-use super::{Component, ComponentDesc};
+use super::ComponentDesc;
 
-#[derive(Clone)]
+/// A sparse set of a single component type, used for components with [`StorageType::SparseSet`](super::StorageType).
+/// Adding and removing components doesn't move the entity's other components around.
 pub struct ComponentStorage {
     /// The pure data that holds the components.
     data: BlobVec,
+    /// When the component in each row was added and last changed.
+    ticks: Vec<UnsafeCell<ComponentTicks>>,
     /// The map from entity to the row index of the component.
     entity_to_row: HashMap<Entity, usize>,
+    /// The entity that owns the component in each row.
+    row_to_entity: Vec<Entity>,
 }
 
 impl ComponentStorage {
@@ -17,13 +27,17 @@ impl ComponentStorage {
         Self {
             // SAFETY: cdesc.drop() is valid for the types that will be inserted.
             data: unsafe { BlobVec::new(cdesc.layout, cdesc.drop, 1) },
+            ticks: Vec::with_capacity(1),
             entity_to_row: HashMap::with_capacity(1),
+            row_to_entity: Vec::with_capacity(1),
         }
     }
 
-    /// Get an iterator over the entities that have components stored in this [`ComponentStorage`].
-    pub(crate) fn entites(&self) -> impl Iterator<Item = &Entity> {
-        self.entity_to_row.keys()
+    /// Clamp the ticks of every component, see [`Tick::check_tick`].
+    pub(crate) fn check_change_ticks(&mut self, this_run: Tick) {
+        for ticks in self.ticks.iter_mut() {
+            ticks.get_mut().check_ticks(this_run);
+        }
     }
 
     /// # SAFETY:
@@ -31,15 +45,52 @@ impl ComponentStorage {
     /// stored here. Or, simply, the type of `val` needs to be the same as the type of components
     /// stored in this [`ComponentStorage`].
     ///
-    /// If the [`Entity`] already had a registered component, override it.
-    pub fn insert(&mut self, entity: Entity, value: OwningPtr<'_>) {
+    /// If the [`Entity`] already had a registered component, override it and mark it as changed.
+    pub fn insert(&mut self, entity: Entity, value: OwningPtr<'_>, change_tick: Tick) {
         if let Some(&index) = self.entity_to_row.get(&entity) {
-            dbg!(index);
             unsafe { self.data.replace_unchecked(index, value) }
+            self.ticks[index].get_mut().changed = change_tick;
         } else {
             unsafe { self.data.push(value) }
+            self.ticks
+                .push(UnsafeCell::new(ComponentTicks::new(change_tick)));
             self.entity_to_row.insert(entity, self.data.len() - 1);
+            self.row_to_entity.push(entity);
+        }
+    }
+
+    /// Remove and drop the component of the given [`Entity`].
+    /// Returns `false` if the entity didn't have a component stored here.
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        let Some(index) = self.unlink_row(entity) else {
+            return false;
+        };
+        // SAFETY: The index was taken from `entity_to_row`, so it's in bounds.
+        unsafe { self.data.swap_remove_and_drop_unchecked(index) };
+        self.ticks.swap_remove(index);
+        true
+    }
+
+    /// Remove the component of the given [`Entity`] without dropping it, it's up to the
+    /// caller to read or drop the value behind the returned pointer.
+    ///
+    /// The pointer is only valid until the next time a component is inserted into this storage.
+    pub fn take(&mut self, entity: Entity) -> Option<OwningPtr<'_>> {
+        let index = self.unlink_row(entity)?;
+        self.ticks.swap_remove(index);
+        // SAFETY: The index was taken from `entity_to_row`, so it's in bounds.
+        unsafe { Some(self.data.swap_remove_and_forget_unchecked(index)) }
+    }
+
+    /// Forget the row of the [`Entity`], and point the last row's entity (which is about to be
+    /// swapped into the removed row) to its new index.
+    fn unlink_row(&mut self, entity: Entity) -> Option<usize> {
+        let index = self.entity_to_row.remove(&entity)?;
+        self.row_to_entity.swap_remove(index);
+        if let Some(&moved) = self.row_to_entity.get(index) {
+            self.entity_to_row.insert(moved, index);
         }
+        Some(index)
     }
 
     /// Get (type erased) shared access to the component for the given [`Entity`]
@@ -53,6 +104,11 @@ impl ComponentStorage {
         unsafe { Some(self.data.get_unchecked(index)) }
     }
 
+    /// Get the ticks of the component for the given [`Entity`]
+    pub fn get_ticks(&self, entity: Entity) -> Option<&UnsafeCell<ComponentTicks>> {
+        self.ticks.get(*self.entity_to_row.get(&entity)?)
+    }
+
     /// Get (type erased) exclusive access to the component for the given [`Entity`]
     pub fn get_mut(&mut self, entity: Entity) -> Option<PtrMut<'_>> {
         let index = *self.entity_to_row.get(&entity)?;
@@ -63,18 +119,4 @@ impl ComponentStorage {
         // SAFETY: We checked that the index isn't greater then the length of the data.
         unsafe { Some(self.data.get_unchecked_mut(index)) }
     }
-
-    /// Get exclusive access to the component for the given [`Entity`]
-    /// # SAFETY:
-    /// `C` must be the same type as the components stored in this [`ComponentStorage`].
-    pub unsafe fn get_mut_typed<C: Component>(&mut self, entity: Entity) -> Option<&mut C> {
-        self.get_mut(entity).map(|p| p.deref_mut::<C>())
-    }
-
-    /// Get shared access to the component for the given [`Entity`]
-    /// # SAFETY:
-    /// `C` must be the same type as the components stored in this [`ComponentStorage`].
-    pub unsafe fn get_typed<C: Component>(&self, entity: Entity) -> Option<&C> {
-        self.get(entity).map(|p| p.deref::<C>())
-    }
 }
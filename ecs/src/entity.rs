@@ -1,17 +1,42 @@
-use crate::{component::Bundle, prelude::World};
+mod allocator;
+mod hierarchy;
+
+use crate::{
+    archetype::{ArchetypeId, EntityLocation, Table},
+    change_detection::ComponentTicks,
+    component::{comp_id, Bundle, Component, ComponentId, ComponentStorage},
+    prelude::World,
+};
 use bevy_ptr::OwningPtr;
+use bevy_utils::HashMap;
 use std::ptr::NonNull;
 
+pub(crate) use allocator::Entities;
+pub use hierarchy::{ChildBuilder, Children, Parent};
+
 /// This struct is used to identify each entity.
-#[repr(transparent)]
+///
+/// The index is reused after an entity is despawned, the generation is what tells
+/// a stale [`Entity`] apart from the one that currently lives at that index.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Entity {
-    id: u32,
+    index: u32,
+    generation: u32,
 }
 
 impl Entity {
-    pub(crate) fn from_raw(raw: u32) -> Self {
-        Self { id: raw }
+    pub(crate) fn new(index: u32, generation: u32) -> Self {
+        Self { index, generation }
+    }
+
+    /// The index of the entity, unique among all the alive entities.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// How many times the index of this entity has been reused.
+    pub fn generation(&self) -> u32 {
+        self.generation
     }
 }
 
@@ -29,35 +54,218 @@ impl<'w> EntityWorldMut<'w> {
         self.entity
     }
 
+    /// Where the entity is stored, the [`EntityWorldMut`] is only created for alive entities.
+    pub fn location(&self) -> EntityLocation {
+        self.world.entities.location(self.entity).unwrap()
+    }
+
+    /// Check if the [`Entity`] has the component.
+    pub fn contains<C: Component>(&self) -> bool {
+        self.world
+            .archetypes
+            .get(self.location().archetype_id)
+            .contains(comp_id::<C>())
+    }
+
     /// insert a [`Bundle`] to the [`Entity`], if the entity already had a component in the bundle, replace it.
     pub fn insert<B: Bundle>(&mut self, bundle: B) -> &mut Self {
-        for boxed_component in bundle.components() {
+        let components = bundle.components();
+        for component in components.iter() {
             self.world.register_component_from_desc(
-                boxed_component.comp_id(),
-                boxed_component.layout(),
-                boxed_component.name(),
-                boxed_component.drop_fn(),
+                component.comp_id(),
+                component.layout(),
+                component.name(),
+                component.drop_fn(),
+                component.storage_type(),
             );
+        }
+
+        let mut comp_ids = self
+            .world
+            .archetypes
+            .get(self.location().archetype_id)
+            .component_ids()
+            .to_vec();
+        comp_ids.extend(components.iter().map(|component| component.comp_id()));
+        let new_archetype_id = self.world.archetypes.get_or_insert(
+            comp_ids,
+            &self.world.components,
+            &mut self.world.tables,
+        );
+        let location = self.move_to_archetype(new_archetype_id, true);
+        let change_tick = self.world.change_tick();
+        let table = self.world.tables.get_mut(location.table_id);
+
+        for boxed_component in components {
             let comp_id = boxed_component.comp_id();
+            let layout = boxed_component.layout();
             // Convert the box into a type-less pointer.
             let raw_pointer_to_component_on_the_heap = Box::into_raw(boxed_component) as *mut u8;
             // Create a new NonNull pointer.
             // SAFETY: We just defined the pointer
             let non_null_pointer_to_component_on_the_heap =
                 NonNull::new(raw_pointer_to_component_on_the_heap).unwrap();
-            // SAFETY: The components types are guarenteed to match.
+            // SAFETY: The components types are guarenteed to match. If the entity already had
+            // the component it has a row in the column, otherwise the column is one row short.
             unsafe {
-                self.world.components.get_mut(&comp_id).unwrap().insert(
-                    self.entity,
-                    OwningPtr::new(non_null_pointer_to_component_on_the_heap),
-                );
+                let value = OwningPtr::new(non_null_pointer_to_component_on_the_heap);
+                match table.column_mut(comp_id) {
+                    Some(column) if location.table_row < column.len() => {
+                        column.replace(location.table_row, value, change_tick)
+                    }
+                    Some(column) => column.push(value, ComponentTicks::new(change_tick)),
+                    None => self.world.sparse_sets.get_mut(&comp_id).unwrap().insert(
+                        self.entity,
+                        value,
+                        change_tick,
+                    ),
+                }
+            }
+            // The component was moved into the storage, free the box without dropping its content.
+            if layout.size() != 0 {
+                // SAFETY: The pointer was allocated by the box with the layout of the component.
+                unsafe { std::alloc::dealloc(raw_pointer_to_component_on_the_heap, layout) };
             }
         }
 
         self
     }
 
+    /// Remove the components of the [`Bundle`] from the [`Entity`] and drop them.
+    /// Components in the bundle that the entity doesn't have are ignored.
+    pub fn remove<B: Bundle>(&mut self) -> &mut Self {
+        let new_archetype_id = self.archetype_without(&B::component_ids());
+        self.move_to_archetype(new_archetype_id, true);
+        self
+    }
+
+    /// Remove the components of the [`Bundle`] from the [`Entity`] and return them.
+    /// Returns `None` (and doesn't remove anything) if the entity doesn't have the whole bundle.
+    pub fn take<B: Bundle>(&mut self) -> Option<B> {
+        let entity = self.entity;
+        let location = self.location();
+        let comp_ids = B::component_ids();
+        let archetype = self.world.archetypes.get(location.archetype_id);
+        if !comp_ids.iter().all(|comp_id| archetype.contains(*comp_id)) {
+            return None;
+        }
+
+        let table = self.world.tables.get_mut(location.table_id) as *mut Table;
+        let sparse_sets =
+            &mut self.world.sparse_sets as *mut HashMap<ComponentId, ComponentStorage>;
+        // SAFETY: We checked that the entity has all the components of the bundle. The values
+        // are moved out of the storages here, and forgotten (not dropped) when the entity moves.
+        let bundle = unsafe {
+            B::from_components(&mut |comp_id| match (*table).column_mut(comp_id) {
+                Some(column) => column.get_mut(location.table_row).unwrap().promote(),
+                None => (*sparse_sets)
+                    .get_mut(&comp_id)
+                    .unwrap()
+                    .get_mut(entity)
+                    .unwrap()
+                    .promote(),
+            })
+        };
+        let new_archetype_id = self.archetype_without(&comp_ids);
+        self.move_to_archetype(new_archetype_id, false);
+        Some(bundle)
+    }
+
+    /// Despawn the [`Entity`] and drop all of its components.
+    pub fn despawn(self) {
+        self.world.despawn(self.entity);
+    }
+
     pub fn world_mut(self) -> &'w mut World {
         self.world
     }
+    /// The archetype the entity would be in without the given components.
+    fn archetype_without(&mut self, removed: &[ComponentId]) -> ArchetypeId {
+        let comp_ids = self
+            .world
+            .archetypes
+            .get(self.location().archetype_id)
+            .component_ids()
+            .iter()
+            .copied()
+            .filter(|comp_id| !removed.contains(comp_id))
+            .collect();
+        self.world.archetypes.get_or_insert(
+            comp_ids,
+            &self.world.components,
+            &mut self.world.tables,
+        )
+    }
+
+    /// Move the entity into a different archetype (and table, if needed), and return its new location.
+    ///
+    /// Components the new archetype doesn't have are dropped if `drop_removed` is true, and
+    /// forgotten otherwise (when they were already moved out).
+    /// Columns of the new table that the old one doesn't have are left one row short,
+    /// and must be pushed to by the caller. The same goes for new sparse-set components.
+    fn move_to_archetype(
+        &mut self,
+        new_archetype_id: ArchetypeId,
+        drop_removed: bool,
+    ) -> EntityLocation {
+        let entity = self.entity;
+        let location = self.location();
+        if location.archetype_id == new_archetype_id {
+            return location;
+        }
+
+        let world = &mut *self.world;
+        let new_archetype = world.archetypes.get(new_archetype_id);
+        let new_table_id = new_archetype.table_id();
+        for comp_id in world.archetypes.get(location.archetype_id).component_ids() {
+            if new_archetype.contains(*comp_id) {
+                continue;
+            }
+            if let Some(sparse_set) = world.sparse_sets.get_mut(comp_id) {
+                if drop_removed {
+                    sparse_set.remove(entity);
+                } else {
+                    let _ = sparse_set.take(entity);
+                }
+            }
+        }
+
+        let table_row = if location.table_id == new_table_id {
+            location.table_row
+        } else {
+            let (old, new) = world.tables.get_2_mut(location.table_id, new_table_id);
+            let new_row = new.push_entity(entity);
+            for comp_id in old.component_ids().to_vec() {
+                let old_column = old.column_mut(comp_id).unwrap();
+                // SAFETY: Every column of the table has a row for each of its entities, and
+                // the columns of both tables store the same component.
+                unsafe {
+                    if let Some(new_column) = new.column_mut(comp_id) {
+                        let (value, ticks) = old_column.swap_remove_and_forget(location.table_row);
+                        new_column.push(value, ticks);
+                    } else if drop_removed {
+                        old_column.swap_remove_and_drop(location.table_row);
+                    } else {
+                        let _ = old_column.swap_remove_and_forget(location.table_row);
+                    }
+                }
+            }
+            world.swap_remove_from_table(location);
+            new_row
+        };
+
+        world.swap_remove_from_archetype(location);
+        let archetype_row = world
+            .archetypes
+            .get_mut(new_archetype_id)
+            .push_entity(entity, table_row);
+        let new_location = EntityLocation {
+            archetype_id: new_archetype_id,
+            archetype_row,
+            table_id: new_table_id,
+            table_row,
+        };
+        world.entities.set_location(entity, new_location);
+        new_location
+    }
 }
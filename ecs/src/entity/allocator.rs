@@ -0,0 +1,148 @@
+use std::sync::atomic::{AtomicIsize, Ordering};
+
+use super::Entity;
+use crate::archetype::EntityLocation;
+
+/// Book-keeping for a single entity index.
+#[derive(Clone, Copy, Debug)]
+struct EntityMeta {
+    /// The generation of the entity currently (or most recently) living at this index.
+    generation: u32,
+    alive: bool,
+    /// Where the entity is stored, only meaningful while it's alive.
+    location: EntityLocation,
+}
+
+/// Allocates [`Entity`] ids. Indices of despawned entities are recycled through a free list,
+/// and their generation is bumped so stale handles can be told apart from the new entity.
+///
+/// Ids can also be reserved through a shared reference (for [`Commands`](crate::prelude::Commands)),
+/// reserved entities only become alive when they are [flushed](Entities::flush).
+#[derive(Default)]
+pub(crate) struct Entities {
+    meta: Vec<EntityMeta>,
+    free_list: Vec<u32>,
+    /// `free_list[..free_cursor]` is still free, the rest of the free list was reserved.
+    /// A negative cursor means that many indices past the end of `meta` were reserved as well.
+    free_cursor: AtomicIsize,
+    len: usize,
+}
+
+impl Entities {
+    /// Allocate a new [`Entity`], reusing a freed index if there is one.
+    /// Reserved entities must be flushed first.
+    pub(crate) fn alloc(&mut self) -> Entity {
+        debug_assert!(!self.needs_flush());
+        self.len += 1;
+        if let Some(index) = self.free_list.pop() {
+            *self.free_cursor.get_mut() = self.free_list.len() as isize;
+            let meta = &mut self.meta[index as usize];
+            meta.alive = true;
+            Entity::new(index, meta.generation)
+        } else {
+            let index = u32::try_from(self.meta.len()).expect("too many entities");
+            self.meta.push(EntityMeta {
+                generation: 0,
+                alive: true,
+                location: EntityLocation::INVALID,
+            });
+            Entity::new(index, 0)
+        }
+    }
+
+    /// Reserve an [`Entity`] id without making it alive, reusing a freed index if there is one.
+    pub(crate) fn reserve(&self) -> Entity {
+        let cursor = self.free_cursor.fetch_sub(1, Ordering::Relaxed);
+        if cursor > 0 {
+            let index = self.free_list[cursor as usize - 1];
+            Entity::new(index, self.meta[index as usize].generation)
+        } else {
+            let index = self.meta.len() as isize - cursor;
+            Entity::new(u32::try_from(index).expect("too many entities"), 0)
+        }
+    }
+
+    /// Check if there are reserved entities that weren't flushed yet.
+    pub(crate) fn needs_flush(&mut self) -> bool {
+        *self.free_cursor.get_mut() != self.free_list.len() as isize
+    }
+
+    /// Make all the reserved entities alive, and return them.
+    /// Their location is invalid until the caller sets it.
+    pub(crate) fn flush(&mut self) -> Vec<Entity> {
+        let cursor = *self.free_cursor.get_mut();
+        let mut flushed = Vec::new();
+        let free_len = if cursor < 0 {
+            let first_new = self.meta.len();
+            for index in first_new..first_new + cursor.unsigned_abs() {
+                self.meta.push(EntityMeta {
+                    generation: 0,
+                    alive: true,
+                    location: EntityLocation::INVALID,
+                });
+                flushed.push(Entity::new(index as u32, 0));
+            }
+            0
+        } else {
+            cursor as usize
+        };
+        for index in self.free_list.drain(free_len..).rev() {
+            let meta = &mut self.meta[index as usize];
+            meta.alive = true;
+            flushed.push(Entity::new(index, meta.generation));
+        }
+        *self.free_cursor.get_mut() = self.free_list.len() as isize;
+        self.len += flushed.len();
+        flushed
+    }
+
+    /// Free the [`Entity`] so its index can be reused.
+    /// Returns `false` if the entity wasn't alive in the first place. Reserved entities must be flushed first.
+    pub(crate) fn free(&mut self, entity: Entity) -> bool {
+        debug_assert!(!self.needs_flush());
+        if !self.contains(entity) {
+            return false;
+        }
+        let meta = &mut self.meta[entity.index() as usize];
+        meta.alive = false;
+        meta.location = EntityLocation::INVALID;
+        meta.generation = meta.generation.wrapping_add(1);
+        self.free_list.push(entity.index());
+        *self.free_cursor.get_mut() = self.free_list.len() as isize;
+        self.len -= 1;
+        true
+    }
+
+    /// Check if the [`Entity`] is alive. Stale handles (from a previous generation) aren't.
+    pub(crate) fn contains(&self, entity: Entity) -> bool {
+        self.meta
+            .get(entity.index() as usize)
+            .is_some_and(|meta| meta.alive && meta.generation == entity.generation())
+    }
+
+    /// Get the location of the [`Entity`], or `None` if it isn't alive.
+    pub(crate) fn location(&self, entity: Entity) -> Option<EntityLocation> {
+        self.contains(entity)
+            .then(|| self.meta[entity.index() as usize].location)
+    }
+
+    /// Set the location of an alive [`Entity`].
+    pub(crate) fn set_location(&mut self, entity: Entity, location: EntityLocation) {
+        debug_assert!(self.contains(entity));
+        self.meta[entity.index() as usize].location = location;
+    }
+
+    /// The amount of alive entities.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Iterate over all the alive entities, ordered by their index.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.meta
+            .iter()
+            .enumerate()
+            .filter(|(_, meta)| meta.alive)
+            .map(|(index, meta)| Entity::new(index as u32, meta.generation))
+    }
+}
@@ -0,0 +1,166 @@
+use crate::prelude::*;
+
+/// The parent of an [`Entity`], kept in sync with the parent's [`Children`] by
+/// [`EntityWorldMut::set_parent`] and [`EntityWorldMut::with_children`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parent(Entity);
+
+impl Parent {
+    pub fn get(&self) -> Entity {
+        self.0
+    }
+}
+
+/// The children of an [`Entity`], in the order they were added.
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Children(Vec<Entity>);
+
+impl std::ops::Deref for Children {
+    type Target = [Entity];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Spawns the children of an entity, see [`EntityWorldMut::with_children`].
+pub struct ChildBuilder<'w> {
+    world: &'w mut World,
+    parent: Entity,
+}
+
+impl ChildBuilder<'_> {
+    /// Spawn a child with the bundle.
+    pub fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityWorldMut<'_> {
+        let parent = self.parent;
+        let mut child = self.world.spawn(bundle);
+        child.set_parent(parent);
+        child
+    }
+
+    /// The entity the children are spawned for.
+    pub fn parent_entity(&self) -> Entity {
+        self.parent
+    }
+}
+
+impl<'w> EntityWorldMut<'w> {
+    /// Make the entity a child of `parent`, removing it from the children of its previous parent.
+    pub fn set_parent(&mut self, parent: Entity) -> &mut Self {
+        let child = self.entity;
+        assert_ne!(
+            child, parent,
+            "The entity {:?} can't be its own parent",
+            child
+        );
+        assert!(
+            !self.world.is_ancestor_of(child, parent),
+            "The entity {:?} can't be the parent of its ancestor {:?}",
+            parent,
+            child
+        );
+        self.remove_parent();
+        let world = &mut *self.world;
+        match world.get_component_mut::<Children>(parent) {
+            Some(mut children) => children.0.push(child),
+            None => {
+                world.entity(parent).insert(Children(vec![child]));
+            }
+        }
+        self.insert(Parent(parent))
+    }
+
+    /// Detach the entity from its parent, if it has one.
+    pub fn remove_parent(&mut self) -> &mut Self {
+        let child = self.entity;
+        let Some(parent) = self.world.get_component::<Parent>(child).map(Parent::get) else {
+            return self;
+        };
+        self.world.remove_child(parent, child);
+        self.remove::<Parent>()
+    }
+
+    /// Spawn children for the entity with the [`ChildBuilder`].
+    pub fn with_children(&mut self, spawn_children: impl FnOnce(&mut ChildBuilder)) -> &mut Self {
+        spawn_children(&mut ChildBuilder {
+            world: self.world,
+            parent: self.entity,
+        });
+        self
+    }
+
+    /// Despawn the entity along with all its descendants.
+    pub fn despawn_recursive(self) {
+        self.world.despawn_recursive(self.entity);
+    }
+}
+
+impl World {
+    /// Despawn the entity along with all its descendants, and remove it from the children of its
+    /// parent. Returns `false` if the entity doesn't exist.
+    pub fn despawn_recursive(&mut self, entity: Entity) -> bool {
+        if let Some(children) = self.get_component::<Children>(entity) {
+            for child in children.0.clone() {
+                self.despawn_recursive(child);
+            }
+        }
+        self.despawn(entity)
+    }
+
+    /// Unlink an entity about to be despawned from the hierarchy: remove it from the children of
+    /// its parent, and its children become roots.
+    pub(crate) fn detach_from_hierarchy(&mut self, entity: Entity) {
+        if let Some(parent) = self.get_component::<Parent>(entity).map(Parent::get) {
+            self.remove_child(parent, entity);
+        }
+        if let Some(children) = self.get_component::<Children>(entity) {
+            for child in children.0.clone() {
+                self.entity(child).remove::<Parent>();
+            }
+        }
+    }
+
+    /// Remove the child from the [`Children`] of the parent, and the component once it's empty.
+    fn remove_child(&mut self, parent: Entity, child: Entity) {
+        let Some(mut children) = self.get_component_mut::<Children>(parent) else {
+            return;
+        };
+        children.0.retain(|&other| other != child);
+        let is_empty = children.0.is_empty();
+        if is_empty {
+            self.entity(parent).remove::<Children>();
+        }
+    }
+
+    /// Check if `ancestor` is the parent of `entity`, or an ancestor of its parent.
+    fn is_ancestor_of(&self, ancestor: Entity, entity: Entity) -> bool {
+        let mut current = entity;
+        while let Some(parent) = self.get_component::<Parent>(current) {
+            if parent.0 == ancestor {
+                return true;
+            }
+            current = parent.0;
+        }
+        false
+    }
+}
+
+impl EntityCommands<'_, '_> {
+    /// Make the entity a child of `parent` when the commands are applied, see
+    /// [`EntityWorldMut::set_parent`].
+    pub fn set_parent(&mut self, parent: Entity) -> &mut Self {
+        let entity = self.id();
+        self.commands().add(move |world: &mut World| {
+            world.entity(entity).set_parent(parent);
+        });
+        self
+    }
+
+    /// Despawn the entity and its descendants, if it still exists.
+    pub fn despawn_recursive(&mut self) {
+        let entity = self.id();
+        self.commands().add(move |world: &mut World| {
+            world.despawn_recursive(entity);
+        });
+    }
+}
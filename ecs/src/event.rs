@@ -0,0 +1,189 @@
+use crate::prelude::*;
+use crate::resource::res_id;
+
+/// Anything that can be sent between systems through [`Events`].
+pub trait Event: Send + Sync + 'static {}
+
+impl<T: Send + Sync + 'static> Event for T {}
+
+/// An event, and the change tick of the world when it was sent.
+struct EventInstance<T> {
+    event: T,
+    tick: Tick,
+}
+
+/// A double-buffered channel of events of type `T`.
+///
+/// Events are kept for two [`World::update`]s (see [`World::add_event`]), so every system gets a
+/// chance to read them regardless of the order the systems run in. [`EventReader`]s only see the
+/// events sent since their system last ran.
+#[derive(Resource)]
+pub struct Events<T: Event> {
+    /// The events sent during the previous update.
+    previous: Vec<EventInstance<T>>,
+    /// The events sent during the current update.
+    current: Vec<EventInstance<T>>,
+}
+
+impl<T: Event> Default for Events<T> {
+    fn default() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+        }
+    }
+}
+
+impl<T: Event> Events<T> {
+    /// Send an event, `change_tick` is the tick readers compare against their last run.
+    pub fn send(&mut self, event: T, change_tick: Tick) {
+        self.current.push(EventInstance {
+            event,
+            tick: change_tick,
+        });
+    }
+
+    /// Drop the events of the previous update, and make the current events the previous ones.
+    pub fn update(&mut self) {
+        self.previous = std::mem::take(&mut self.current);
+    }
+
+    /// Drop all the events.
+    pub fn clear(&mut self) {
+        self.previous.clear();
+        self.current.clear();
+    }
+
+    /// Iterate over all the stored events, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.instances().map(|instance| &instance.event)
+    }
+
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn instances(&self) -> impl Iterator<Item = &EventInstance<T>> + '_ {
+        self.previous.iter().chain(self.current.iter())
+    }
+
+    /// The events sent after `last_run`, as seen from `this_run`.
+    fn iter_since(&self, last_run: Tick, this_run: Tick) -> impl Iterator<Item = &T> + '_ {
+        self.instances()
+            .filter(move |instance| instance.tick.is_newer_than(last_run, this_run))
+            .map(|instance| &instance.event)
+    }
+}
+
+/// A [`SystemParam`] for sending events of type `T`.
+pub struct EventWriter<'w, T: Event> {
+    events: ResMut<'w, Events<T>>,
+    this_run: Tick,
+}
+
+impl<'w, T: Event> EventWriter<'w, T> {
+    pub fn send(&mut self, event: T) {
+        self.events.send(event, self.this_run);
+    }
+
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = T>) {
+        for event in events {
+            self.send(event);
+        }
+    }
+}
+
+/// A [`SystemParam`] for reading the events of type `T` sent since the system last ran.
+pub struct EventReader<'w, T: Event> {
+    events: Res<'w, Events<T>>,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+impl<'w, T: Event> EventReader<'w, T> {
+    /// Iterate over the unread events, oldest first. The events are marked as read,
+    /// so reading again during the same run doesn't return them.
+    pub fn read(&mut self) -> impl Iterator<Item = &T> + '_ {
+        let last_run = std::mem::replace(&mut self.last_run, self.this_run);
+        self.events.iter_since(last_run, self.this_run)
+    }
+
+    /// The amount of unread events.
+    pub fn len(&self) -> usize {
+        self.events.iter_since(self.last_run, self.this_run).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Mark all the events as read.
+    pub fn clear(&mut self) {
+        self.last_run = self.this_run;
+    }
+}
+
+unsafe impl<T: Event> SystemParam for EventWriter<'_, T> {
+    type Item<'a> = EventWriter<'a, T>;
+
+    type State = <ResMut<'static, Events<T>> as SystemParam>::State;
+
+    fn init_state(world: &mut World) -> Self::State {
+        ResMut::<Events<T>>::init_state(world)
+    }
+
+    unsafe fn fetch_from_world<'a>(
+        state: &'a mut Self::State,
+        world: UnsafeWorldCell<'a>,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Item<'a> {
+        EventWriter {
+            events: ResMut::fetch_from_world(state, world, last_run, this_run),
+            this_run,
+        }
+    }
+
+    unsafe fn validate(state: &Self::State, world: UnsafeWorldCell<'_>) -> Result<(), ParamError> {
+        ResMut::<Events<T>>::validate(state, world)
+    }
+
+    fn access_table() -> AccessTable {
+        AccessTable::single(res_id::<Events<T>>(), Access::Write)
+    }
+}
+
+unsafe impl<T: Event> SystemParam for EventReader<'_, T> {
+    type Item<'a> = EventReader<'a, T>;
+
+    type State = <Res<'static, Events<T>> as SystemParam>::State;
+
+    fn init_state(world: &mut World) -> Self::State {
+        Res::<Events<T>>::init_state(world)
+    }
+
+    unsafe fn fetch_from_world<'a>(
+        state: &'a mut Self::State,
+        world: UnsafeWorldCell<'a>,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Item<'a> {
+        EventReader {
+            events: Res::fetch_from_world(state, world, last_run, this_run),
+            last_run,
+            this_run,
+        }
+    }
+
+    unsafe fn validate(state: &Self::State, world: UnsafeWorldCell<'_>) -> Result<(), ParamError> {
+        Res::<Events<T>>::validate(state, world)
+    }
+
+    fn access_table() -> AccessTable {
+        AccessTable::single(res_id::<Events<T>>(), Access::Read)
+    }
+}
@@ -1,19 +1,38 @@
 #![allow(unused_imports)]
 
+mod archetype;
+mod change_detection;
 mod component;
 mod entity;
+mod event;
 mod query;
 mod resource;
+mod state;
 mod system;
+mod time;
 pub(crate) mod utils;
 mod world;
 
 pub mod prelude {
-    pub use crate::component::{comp_id, drop_ptr, Bundle, Component, ComponentId};
-    pub use crate::entity::{Entity, EntityWorldMut};
-    pub use crate::query::{Query, QueryIter, WorldQuery};
+    pub use crate::archetype::{
+        Archetype, ArchetypeId, Archetypes, Column, EntityLocation, Table, TableId, Tables,
+    };
+    pub use crate::change_detection::{
+        ComponentTicks, Mut, Ref, Tick, CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE,
+    };
+    pub use crate::component::{comp_id, drop_ptr, Bundle, Component, ComponentId, StorageType};
+    pub use crate::entity::{ChildBuilder, Children, Entity, EntityWorldMut, Parent};
+    pub use crate::event::{Event, EventReader, EventWriter, Events};
+    pub use crate::query::{
+        Added, ArchetypeFilter, Changed, Or, Query, QueryFilter, QueryIter, QueryState,
+        ReadOnlyWorldQuery, With, Without, WorldQuery,
+    };
     pub use crate::resource::prelude::*;
+    pub use crate::state::{in_state, NextState, OnEnter, OnExit, OnTransition, State, States};
     pub use crate::system::*;
+    pub use crate::time::{
+        run_fixed_update_schedule, update_time, Fixed, Real, Time, TimeUpdateStrategy, Virtual,
+    };
     pub use crate::world::*;
     pub use bevy_ptr::{OwningPtr, Ptr};
     pub use derive::*;
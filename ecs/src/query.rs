@@ -1,110 +1,285 @@
+mod filter;
+mod state;
+
 use std::any::TypeId;
+use std::borrow::Cow;
+use std::cell::UnsafeCell;
 
 use bevy_utils::all_tuples;
 
 use crate::prelude::*;
 use crate::{
-    component::{comp_id, Component},
+    change_detection::{ComponentTicks, Mut, Ref, Tick},
+    component::{comp_id, Component, ComponentStorage},
     prelude::{unsafe_world_cell::UnsafeWorldCell, AccessTable, Entity, World},
 };
 
 #[derive(Debug)]
 pub enum QueryError {
     EntityNotInQuery,
+    /// The entity was despawned, or the handle is stale.
+    EntityDoesntExist,
     ErrorFetchingData,
 }
 
+pub use filter::*;
+pub use state::QueryState;
+
 pub type QueryResult<T> = Result<T, QueryError>;
 
 /// Represents data a query can fetch from the [`World`].
 pub trait WorldQuery {
     type Data<'a>;
 
-    fn get_data_from_world<'w>(
+    /// What's needed to fetch the data from the rows of a single [`Archetype`].
+    type Fetch<'w>;
+
+    /// Check if the entities of the [`Archetype`] have everything needed to fetch the data.
+    fn matches_archetype(archetype: &Archetype) -> bool;
+
+    /// Prepare to fetch the data from the rows of the [`Archetype`]. Changes are detected
+    /// relative to `last_run`, and marked at `this_run`.
+    ///
+    /// # Safety
+    /// The archetype must match the query, and the caller must ensure the [`Access`] described
+    /// by [`WorldQuery::access_table`] doesn't conflict with any other reference to the world.
+    unsafe fn init_fetch<'w>(
         world: UnsafeWorldCell<'w>,
+        archetype: &'w Archetype,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w>;
+
+    /// Fetch the data of the entity, whose table components are at `table_row`.
+    ///
+    /// # Safety
+    /// `fetch` must have been initialized for the archetype of the entity, and `table_row` must be its table row.
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
         entity: Entity,
-    ) -> QueryResult<Self::Data<'w>>;
-
-    fn get_entites_matching_archetype<'w>(world: UnsafeWorldCell<'w>) -> Vec<Entity>;
+        table_row: usize,
+    ) -> Self::Data<'w>;
 
     fn access_table() -> AccessTable;
+
+    fn get_data_from_world<'w>(
+        world: UnsafeWorldCell<'w>,
+        entity: Entity,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> QueryResult<Self::Data<'w>> {
+        let location = world
+            .entity_location(entity)
+            .ok_or(QueryError::EntityDoesntExist)?;
+        let archetype = world.archetypes().get(location.archetype_id);
+        if !Self::matches_archetype(archetype) {
+            return Err(QueryError::EntityNotInQuery);
+        }
+        // SAFETY: We checked that the archetype matches, and the location is of this entity.
+        unsafe {
+            let mut fetch = Self::init_fetch(world, archetype, last_run, this_run);
+            Ok(Self::fetch(&mut fetch, entity, location.table_row))
+        }
+    }
 }
 
-pub struct Query<'w, Q: WorldQuery> {
-    entites: Vec<Entity>,
+/// A [`WorldQuery`] that only reads the world, so its data can be fetched through a shared
+/// borrow of the query, and aliased.
+///
+/// # Safety
+/// The data must not allow mutating the world, and [`WorldQuery::access_table`] must only read.
+pub unsafe trait ReadOnlyWorldQuery: WorldQuery {}
+
+/// Fetches the [`WorldQuery`] data of all the entities that pass the [`QueryFilter`].
+pub struct Query<'w, Q: WorldQuery, F: QueryFilter = ()> {
+    /// The matching archetypes, borrowed from the [`QueryState`] of a system.
+    archetypes: Cow<'w, [ArchetypeId]>,
     world: UnsafeWorldCell<'w>,
-    _marker: std::marker::PhantomData<Q>,
+    last_run: Tick,
+    this_run: Tick,
+    _marker: std::marker::PhantomData<(Q, F)>,
 }
 
-impl<'w, Q: WorldQuery> Query<'w, Q> {
-    pub fn get(&self, entity: Entity) -> QueryResult<Q::Data<'_>> {
-        Q::get_data_from_world(self.world, entity)
+impl<'w, Q: WorldQuery, F: QueryFilter> Query<'w, Q, F> {
+    /// The data of the entity, if it matches the query. Only for read-only queries, see
+    /// [`Query::get_mut`] for the others.
+    pub fn get(&self, entity: Entity) -> QueryResult<Q::Data<'_>>
+    where
+        Q: ReadOnlyWorldQuery,
+    {
+        // SAFETY: The query only reads, so the data can alias other data fetched through it.
+        unsafe { self.get_unchecked(entity) }
+    }
+
+    /// Like [`Query::get`], borrowing the query mutably so the data can be mutated.
+    pub fn get_mut(&mut self, entity: Entity) -> QueryResult<Q::Data<'_>> {
+        // SAFETY: The data can't outlive the exclusive borrow of the query.
+        unsafe { self.get_unchecked(entity) }
+    }
+
+    /// Like [`Query::get`], consuming the query to return data that lives as long as the world.
+    pub(crate) fn get_inner(self, entity: Entity) -> QueryResult<Q::Data<'w>> {
+        // SAFETY: The query is consumed, so the data can't alias data fetched through it later.
+        unsafe { self.get_unchecked(entity) }
+    }
+
+    /// # Safety
+    /// The caller must ensure the data doesn't alias other data fetched through the query.
+    unsafe fn get_unchecked(&self, entity: Entity) -> QueryResult<Q::Data<'w>> {
+        let location = self
+            .world
+            .entity_location(entity)
+            .ok_or(QueryError::EntityDoesntExist)?;
+        let archetype = self.world.archetypes().get(location.archetype_id);
+        if !Q::matches_archetype(archetype) || !F::matches_archetype(archetype) {
+            return Err(QueryError::EntityNotInQuery);
+        }
+        // SAFETY: We checked that the archetype matches, and the location is of this entity.
+        unsafe {
+            let mut filter = F::init_fetch(self.world, archetype, self.last_run, self.this_run);
+            if !F::filter_fetch(&mut filter, entity, location.table_row) {
+                return Err(QueryError::EntityNotInQuery);
+            }
+            let mut fetch = Q::init_fetch(self.world, archetype, self.last_run, self.this_run);
+            Ok(Q::fetch(&mut fetch, entity, location.table_row))
+        }
     }
 }
 
-impl<'w, Q: WorldQuery> Query<'w, Q> {
-    pub(crate) fn from_world(world: &'w World) -> Self {
-        let world = UnsafeWorldCell::from_world(world);
+impl<'w, Q: WorldQuery, F: QueryFilter> Query<'w, Q, F> {
+    /// Changes are detected relative to `last_run`, and marked at `this_run`.
+    pub(crate) fn new(
+        world: &'w World,
+        archetypes: Cow<'w, [ArchetypeId]>,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self {
         Self {
-            entites: Q::get_entites_matching_archetype(world),
-            world,
+            archetypes,
+            world: UnsafeWorldCell::from_world(world),
+            last_run,
+            this_run,
             _marker: std::marker::PhantomData,
         }
     }
 }
 
-impl<'w, Q: WorldQuery> IntoIterator for Query<'w, Q> {
+impl<'w, Q: WorldQuery, F: QueryFilter> Query<'w, Q, F> {
+    /// A query matching the archetypes from scratch, without a cached [`QueryState`].
+    pub(crate) fn from_world(world: &'w World, last_run: Tick, this_run: Tick) -> Self {
+        let archetypes = QueryState::<Q, F>::new(world).into_matched_archetypes();
+        Self::new(world, Cow::Owned(archetypes), last_run, this_run)
+    }
+}
+
+impl<'w, Q: WorldQuery, F: QueryFilter> IntoIterator for Query<'w, Q, F> {
     type Item = Q::Data<'w>;
-    type IntoIter = QueryIter<'w, Q>;
+    type IntoIter = QueryIter<'w, Q, F>;
 
     fn into_iter(self) -> Self::IntoIter {
+        let archetypes = self.world.archetypes();
+        let remaining = self
+            .archetypes
+            .iter()
+            .map(|id| archetypes.get(*id).len())
+            .sum();
         QueryIter {
-            entites: self.entites,
-            current_entity: 0,
+            archetypes: self.archetypes,
+            next_archetype: 0,
+            current: None,
+            current_row: 0,
+            remaining,
             world: self.world,
-            _marker: std::marker::PhantomData,
+            last_run: self.last_run,
+            this_run: self.this_run,
         }
     }
 }
 
-pub struct QueryIter<'w, Q: WorldQuery> {
-    entites: Vec<Entity>,
-    current_entity: usize,
+pub struct QueryIter<'w, Q: WorldQuery, F: QueryFilter = ()> {
+    archetypes: Cow<'w, [ArchetypeId]>,
+    next_archetype: usize,
+    /// The archetype being iterated, and the fetches initialized for it.
+    current: Option<(&'w Archetype, Q::Fetch<'w>, F::Fetch<'w>)>,
+    current_row: usize,
+    /// The amount of entities left in the matching archetypes, some may still be filtered out.
+    remaining: usize,
     world: UnsafeWorldCell<'w>,
-    _marker: std::marker::PhantomData<Q>,
+    last_run: Tick,
+    this_run: Tick,
 }
 
-impl<'w, Q: WorldQuery> ExactSizeIterator for QueryIter<'w, Q> {
+impl<'w, Q: WorldQuery, F: ArchetypeFilter> ExactSizeIterator for QueryIter<'w, Q, F> {
     fn len(&self) -> usize {
-        self.entites.len()
+        self.remaining
     }
 }
 
-impl<'w, Q: WorldQuery> Iterator for QueryIter<'w, Q> {
+impl<'w, Q: WorldQuery, F: QueryFilter> Iterator for QueryIter<'w, Q, F> {
     type Item = Q::Data<'w>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current_entity >= self.entites.len() {
-            return None;
+        loop {
+            if let Some((archetype, fetch, filter)) = &mut self.current {
+                while self.current_row < archetype.len() {
+                    let row = self.current_row;
+                    self.current_row += 1;
+                    self.remaining -= 1;
+                    let entity = archetype.entities()[row];
+                    let table_row = archetype.table_rows()[row];
+                    // SAFETY: The fetches were initialized for this archetype, and the row is in bounds.
+                    unsafe {
+                        if F::filter_fetch(filter, entity, table_row) {
+                            return Some(Q::fetch(fetch, entity, table_row));
+                        }
+                    }
+                }
+            }
+            let archetype_id = *self.archetypes.get(self.next_archetype)?;
+            self.next_archetype += 1;
+            let archetype = self.world.archetypes().get(archetype_id);
+            // SAFETY: Only matching archetypes are collected by the query.
+            self.current = Some(unsafe {
+                (
+                    archetype,
+                    Q::init_fetch(self.world, archetype, self.last_run, self.this_run),
+                    F::init_fetch(self.world, archetype, self.last_run, self.this_run),
+                )
+            });
+            self.current_row = 0;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if F::IS_ARCHETYPAL {
+            (self.remaining, Some(self.remaining))
+        } else {
+            (0, Some(self.remaining))
         }
-        let entity = self.entites[self.current_entity];
-        self.current_entity += 1;
-        Some(Q::get_data_from_world(self.world, entity).unwrap())
     }
 }
 
+// SAFETY: Entities are copied out of the archetype, nothing is accessed.
+unsafe impl ReadOnlyWorldQuery for Entity {}
+
 impl WorldQuery for Entity {
     type Data<'a> = Entity;
+    type Fetch<'w> = ();
 
-    fn get_data_from_world<'w>(
+    fn matches_archetype(_archetype: &Archetype) -> bool {
+        true
+    }
+
+    unsafe fn init_fetch<'w>(
         _world: UnsafeWorldCell<'w>,
-        entity: Entity,
-    ) -> QueryResult<Self::Data<'w>> {
-        Ok(entity)
+        _archetype: &'w Archetype,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) {
     }
 
-    fn get_entites_matching_archetype<'w>(world: UnsafeWorldCell<'w>) -> Vec<Entity> {
-        world.all_entities()
+    unsafe fn fetch<'w>(_fetch: &mut (), entity: Entity, _table_row: usize) -> Self::Data<'w> {
+        entity
     }
 
     fn access_table() -> AccessTable {
@@ -112,22 +287,134 @@ impl WorldQuery for Entity {
     }
 }
 
+/// Where the fetched component lives, depending on its [`StorageType`].
+enum StorageFetch<'w, C> {
+    Table {
+        data: &'w [UnsafeCell<C>],
+        ticks: &'w [UnsafeCell<ComponentTicks>],
+    },
+    SparseSet(&'w ComponentStorage),
+}
+
+/// Fetches a single component (and its ticks) from the storage it lives in.
+pub struct ComponentFetch<'w, C> {
+    storage: StorageFetch<'w, C>,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+impl<'w, C: Component> ComponentFetch<'w, C> {
+    /// # Safety
+    /// The archetype must contain the component.
+    unsafe fn new(
+        world: UnsafeWorldCell<'w>,
+        archetype: &'w Archetype,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self {
+        let storage = match world
+            .tables()
+            .get(archetype.table_id())
+            .column(comp_id::<C>())
+        {
+            Some(column) => StorageFetch::Table {
+                data: column.get_slice::<C>(),
+                ticks: column.get_ticks_slice(),
+            },
+            None => StorageFetch::SparseSet(world.sparse_set(comp_id::<C>()).unwrap()),
+        };
+        Self {
+            storage,
+            last_run,
+            this_run,
+        }
+    }
+
+    /// # Safety
+    /// The caller must ensure the pointer doesn't alias an exclusive reference.
+    unsafe fn get(&self, entity: Entity, table_row: usize) -> *mut C {
+        match self.storage {
+            StorageFetch::Table { data, .. } => data[table_row].get(),
+            StorageFetch::SparseSet(sparse_set) => {
+                sparse_set.get(entity).unwrap().as_ptr() as *mut C
+            }
+        }
+    }
+
+    /// # Safety
+    /// The caller must ensure the pointer doesn't alias an exclusive reference.
+    unsafe fn get_ticks(&self, entity: Entity, table_row: usize) -> *mut ComponentTicks {
+        match self.storage {
+            StorageFetch::Table { ticks, .. } => ticks[table_row].get(),
+            StorageFetch::SparseSet(sparse_set) => sparse_set.get_ticks(entity).unwrap().get(),
+        }
+    }
+}
+
+// SAFETY: Shared references only read the component.
+unsafe impl<C: Component> ReadOnlyWorldQuery for &C {}
+
 impl<C: Component> WorldQuery for &C {
     type Data<'w> = &'w C;
+    type Fetch<'w> = ComponentFetch<'w, C>;
 
-    fn get_data_from_world<'w>(
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        archetype.contains(comp_id::<C>())
+    }
+
+    unsafe fn init_fetch<'w>(
         world: UnsafeWorldCell<'w>,
+        archetype: &'w Archetype,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        ComponentFetch::new(world, archetype, last_run, this_run)
+    }
+
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
         entity: Entity,
-    ) -> QueryResult<Self::Data<'w>> {
-        world
-            .get_component::<C>(entity)
-            .ok_or(QueryError::ErrorFetchingData)
+        table_row: usize,
+    ) -> Self::Data<'w> {
+        &*fetch.get(entity, table_row)
+    }
+
+    fn access_table() -> AccessTable {
+        AccessTable::single(comp_id::<C>(), Access::Read)
     }
+}
+
+// SAFETY: `Ref` only reads the component and its ticks.
+unsafe impl<C: Component> ReadOnlyWorldQuery for Ref<'_, C> {}
+
+impl<C: Component> WorldQuery for Ref<'_, C> {
+    type Data<'w> = Ref<'w, C>;
+    type Fetch<'w> = ComponentFetch<'w, C>;
 
-    fn get_entites_matching_archetype(world: UnsafeWorldCell<'_>) -> Vec<Entity> {
-        world
-            .component_storage(comp_id::<C>())
-            .map_or(Vec::new(), |storage| storage.entites().copied().collect())
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        archetype.contains(comp_id::<C>())
+    }
+
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        archetype: &'w Archetype,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        ComponentFetch::new(world, archetype, last_run, this_run)
+    }
+
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: usize,
+    ) -> Self::Data<'w> {
+        Ref {
+            value: &*fetch.get(entity, table_row),
+            ticks: &*fetch.get_ticks(entity, table_row),
+            last_run: fetch.last_run,
+            this_run: fetch.this_run,
+        }
     }
 
     fn access_table() -> AccessTable {
@@ -136,21 +423,33 @@ impl<C: Component> WorldQuery for &C {
 }
 
 impl<C: Component> WorldQuery for &mut C {
-    type Data<'w> = &'w mut C;
+    type Data<'w> = Mut<'w, C>;
+    type Fetch<'w> = ComponentFetch<'w, C>;
 
-    fn get_data_from_world<'w>(
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        archetype.contains(comp_id::<C>())
+    }
+
+    unsafe fn init_fetch<'w>(
         world: UnsafeWorldCell<'w>,
-        entity: Entity,
-    ) -> QueryResult<Self::Data<'_>> {
-        world
-            .get_component_mut::<C>(entity)
-            .ok_or(QueryError::ErrorFetchingData)
+        archetype: &'w Archetype,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        ComponentFetch::new(world, archetype, last_run, this_run)
     }
 
-    fn get_entites_matching_archetype(world: UnsafeWorldCell<'_>) -> Vec<Entity> {
-        world
-            .component_storage(comp_id::<C>())
-            .map_or(Vec::new(), |storage| storage.entites().copied().collect())
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: usize,
+    ) -> Self::Data<'w> {
+        Mut {
+            value: &mut *fetch.get(entity, table_row),
+            ticks: &mut *fetch.get_ticks(entity, table_row),
+            last_run: fetch.last_run,
+            this_run: fetch.this_run,
+        }
     }
 
     fn access_table() -> AccessTable {
@@ -158,27 +457,65 @@ impl<C: Component> WorldQuery for &mut C {
     }
 }
 
+// SAFETY: The data is the data of `Q`, when the entity matches it.
+unsafe impl<Q: ReadOnlyWorldQuery> ReadOnlyWorldQuery for Option<Q> {}
+
+impl<Q: WorldQuery> WorldQuery for Option<Q> {
+    type Data<'w> = Option<Q::Data<'w>>;
+    /// `None` if the archetype doesn't match `Q`.
+    type Fetch<'w> = Option<Q::Fetch<'w>>;
+
+    fn matches_archetype(_archetype: &Archetype) -> bool {
+        true
+    }
+
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        archetype: &'w Archetype,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        Q::matches_archetype(archetype).then(|| Q::init_fetch(world, archetype, last_run, this_run))
+    }
+
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: usize,
+    ) -> Self::Data<'w> {
+        fetch
+            .as_mut()
+            .map(|fetch| Q::fetch(fetch, entity, table_row))
+    }
+
+    fn access_table() -> AccessTable {
+        Q::access_table()
+    }
+}
+
 macro_rules! impl_query_data_for_tuple {
     ($($name:ident),*) => {
+        // SAFETY: The data is the data of each query of the tuple.
+        unsafe impl <$( $name: ReadOnlyWorldQuery ),*> ReadOnlyWorldQuery for ($( $name, )*) {}
+
         impl <$( $name: WorldQuery ),*> WorldQuery for ($( $name, )*) {
             type Data<'w> = ($( $name::Data<'w>, )*);
+            type Fetch<'w> = ($( $name::Fetch<'w>, )*);
 
             #[allow(unused_variables)]
-            fn get_data_from_world<'w>(world: UnsafeWorldCell<'w>, entity: Entity) -> QueryResult<Self::Data<'_>> {
-                Ok(($( $name::get_data_from_world(world, entity)?, )*))
+            fn matches_archetype(archetype: &Archetype) -> bool {
+                true $(&& $name::matches_archetype(archetype))*
             }
 
-            #[allow(unused_mut, non_snake_case)]
-            fn get_entites_matching_archetype(world: UnsafeWorldCell<'_>) -> Vec<Entity> {
-                let mut entites = world.all_entities();
-                $(
-                    let mut $name = $name::get_entites_matching_archetype(world);
-                    $name.sort();
-                )*
-                $(
-                    entites.retain(|e| $name.binary_search(e).is_ok());
-                )*
-                entites
+            #[allow(unused_variables, clippy::unused_unit)]
+            unsafe fn init_fetch<'w>(world: UnsafeWorldCell<'w>, archetype: &'w Archetype, last_run: Tick, this_run: Tick) -> Self::Fetch<'w> {
+                ($( $name::init_fetch(world, archetype, last_run, this_run), )*)
+            }
+
+            #[allow(unused_variables, non_snake_case, clippy::unused_unit)]
+            unsafe fn fetch<'w>(fetch: &mut Self::Fetch<'w>, entity: Entity, table_row: usize) -> Self::Data<'w> {
+                let ($( $name, )*) = fetch;
+                ($( $name::fetch($name, entity, table_row), )*)
             }
 
             #[allow(unused_mut)]
@@ -0,0 +1,247 @@
+use std::marker::PhantomData;
+
+use bevy_utils::all_tuples;
+
+use super::ComponentFetch;
+use crate::change_detection::Tick;
+use crate::component::{comp_id, Component};
+use crate::prelude::{unsafe_world_cell::UnsafeWorldCell, Access, AccessTable, Archetype, Entity};
+
+/// Narrows down the entities a [`Query`](super::Query) matches, without fetching any data.
+pub trait QueryFilter {
+    /// What's needed to filter the rows of a single [`Archetype`].
+    type Fetch<'w>;
+
+    /// True if the filter is decided by [`QueryFilter::matches_archetype`] alone,
+    /// so every entity of a matching archetype passes [`QueryFilter::filter_fetch`].
+    const IS_ARCHETYPAL: bool;
+
+    /// Check if the entities of the [`Archetype`] pass the filter.
+    fn matches_archetype(archetype: &Archetype) -> bool;
+
+    /// Prepare to filter the rows of the [`Archetype`], changes are detected
+    /// relative to `last_run`, as seen from `this_run`.
+    ///
+    /// # Safety
+    /// The archetype must match the filter, and the caller must ensure the [`Access`] described
+    /// by [`QueryFilter::access_table`] doesn't conflict with any other reference to the world.
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        archetype: &'w Archetype,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w>;
+
+    /// Check if the entity, whose table components are at `table_row`, passes the filter.
+    ///
+    /// # Safety
+    /// `fetch` must have been initialized for the archetype of the entity, and `table_row` must be its table row.
+    unsafe fn filter_fetch(fetch: &mut Self::Fetch<'_>, entity: Entity, table_row: usize) -> bool;
+
+    fn access_table() -> AccessTable;
+}
+
+/// A [`QueryFilter`] that only looks at archetypes, which lets the query know its exact length.
+pub trait ArchetypeFilter: QueryFilter {}
+
+/// Only match entities that have the component `T`.
+pub struct With<T>(PhantomData<T>);
+
+/// Only match entities that don't have the component `T`.
+pub struct Without<T>(PhantomData<T>);
+
+/// Match entities that pass at least one of the filters in the tuple.
+pub struct Or<T>(PhantomData<T>);
+
+/// Only match entities whose component `T` was added since the system last ran.
+pub struct Added<T>(PhantomData<T>);
+
+/// Only match entities whose component `T` was changed (or added) since the system last ran.
+pub struct Changed<T>(PhantomData<T>);
+
+impl<T: Component> QueryFilter for With<T> {
+    type Fetch<'w> = ();
+
+    const IS_ARCHETYPAL: bool = true;
+
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        archetype.contains(comp_id::<T>())
+    }
+
+    unsafe fn init_fetch<'w>(
+        _world: UnsafeWorldCell<'w>,
+        _archetype: &'w Archetype,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) {
+    }
+
+    unsafe fn filter_fetch(_fetch: &mut (), _entity: Entity, _table_row: usize) -> bool {
+        true
+    }
+
+    fn access_table() -> AccessTable {
+        AccessTable::new()
+    }
+}
+
+impl<T: Component> ArchetypeFilter for With<T> {}
+
+impl<T: Component> QueryFilter for Without<T> {
+    type Fetch<'w> = ();
+
+    const IS_ARCHETYPAL: bool = true;
+
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        !archetype.contains(comp_id::<T>())
+    }
+
+    unsafe fn init_fetch<'w>(
+        _world: UnsafeWorldCell<'w>,
+        _archetype: &'w Archetype,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) {
+    }
+
+    unsafe fn filter_fetch(_fetch: &mut (), _entity: Entity, _table_row: usize) -> bool {
+        true
+    }
+
+    fn access_table() -> AccessTable {
+        AccessTable::new()
+    }
+}
+
+impl<T: Component> ArchetypeFilter for Without<T> {}
+
+impl<T: Component> QueryFilter for Added<T> {
+    type Fetch<'w> = ComponentFetch<'w, T>;
+
+    const IS_ARCHETYPAL: bool = false;
+
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        archetype.contains(comp_id::<T>())
+    }
+
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        archetype: &'w Archetype,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        ComponentFetch::new(world, archetype, last_run, this_run)
+    }
+
+    unsafe fn filter_fetch(fetch: &mut Self::Fetch<'_>, entity: Entity, table_row: usize) -> bool {
+        (*fetch.get_ticks(entity, table_row))
+            .added
+            .is_newer_than(fetch.last_run, fetch.this_run)
+    }
+
+    fn access_table() -> AccessTable {
+        AccessTable::single(comp_id::<T>(), Access::Read)
+    }
+}
+
+impl<T: Component> QueryFilter for Changed<T> {
+    type Fetch<'w> = ComponentFetch<'w, T>;
+
+    const IS_ARCHETYPAL: bool = false;
+
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        archetype.contains(comp_id::<T>())
+    }
+
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        archetype: &'w Archetype,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        ComponentFetch::new(world, archetype, last_run, this_run)
+    }
+
+    unsafe fn filter_fetch(fetch: &mut Self::Fetch<'_>, entity: Entity, table_row: usize) -> bool {
+        (*fetch.get_ticks(entity, table_row))
+            .changed
+            .is_newer_than(fetch.last_run, fetch.this_run)
+    }
+
+    fn access_table() -> AccessTable {
+        AccessTable::single(comp_id::<T>(), Access::Read)
+    }
+}
+
+macro_rules! impl_query_filter_for_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: QueryFilter),*> QueryFilter for ($($name,)*) {
+            type Fetch<'w> = ($($name::Fetch<'w>,)*);
+
+            const IS_ARCHETYPAL: bool = true $(&& $name::IS_ARCHETYPAL)*;
+
+            #[allow(unused_variables)]
+            fn matches_archetype(archetype: &Archetype) -> bool {
+                true $(&& $name::matches_archetype(archetype))*
+            }
+
+            #[allow(unused_variables, clippy::unused_unit)]
+            unsafe fn init_fetch<'w>(world: UnsafeWorldCell<'w>, archetype: &'w Archetype, last_run: Tick, this_run: Tick) -> Self::Fetch<'w> {
+                ($($name::init_fetch(world, archetype, last_run, this_run),)*)
+            }
+
+            #[allow(unused_variables, non_snake_case)]
+            unsafe fn filter_fetch(fetch: &mut Self::Fetch<'_>, entity: Entity, table_row: usize) -> bool {
+                let ($($name,)*) = fetch;
+                true $(&& $name::filter_fetch($name, entity, table_row))*
+            }
+
+            #[allow(unused_mut)]
+            fn access_table() -> AccessTable {
+                let mut access_table = AccessTable::new();
+                $(
+                    access_table.merge($name::access_table());
+                )*
+                access_table
+            }
+        }
+
+        impl<$($name: ArchetypeFilter),*> ArchetypeFilter for ($($name,)*) {}
+
+        impl<$($name: QueryFilter),*> QueryFilter for Or<($($name,)*)> {
+            /// `None` for the filters the archetype doesn't match.
+            type Fetch<'w> = ($(Option<$name::Fetch<'w>>,)*);
+
+            const IS_ARCHETYPAL: bool = true $(&& $name::IS_ARCHETYPAL)*;
+
+            #[allow(unused_variables)]
+            fn matches_archetype(archetype: &Archetype) -> bool {
+                false $(|| $name::matches_archetype(archetype))*
+            }
+
+            #[allow(unused_variables, clippy::unused_unit)]
+            unsafe fn init_fetch<'w>(world: UnsafeWorldCell<'w>, archetype: &'w Archetype, last_run: Tick, this_run: Tick) -> Self::Fetch<'w> {
+                ($($name::matches_archetype(archetype).then(|| $name::init_fetch(world, archetype, last_run, this_run)),)*)
+            }
+
+            #[allow(unused_variables, non_snake_case)]
+            unsafe fn filter_fetch(fetch: &mut Self::Fetch<'_>, entity: Entity, table_row: usize) -> bool {
+                let ($($name,)*) = fetch;
+                false $(|| $name.as_mut().is_some_and(|fetch| $name::filter_fetch(fetch, entity, table_row)))*
+            }
+
+            #[allow(unused_mut)]
+            fn access_table() -> AccessTable {
+                let mut access_table = AccessTable::new();
+                $(
+                    access_table.merge($name::access_table());
+                )*
+                access_table
+            }
+        }
+
+        impl<$($name: ArchetypeFilter),*> ArchetypeFilter for Or<($($name,)*)> {}
+    };
+}
+
+all_tuples!(impl_query_filter_for_tuple, 0, 15, F);
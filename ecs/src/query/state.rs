@@ -0,0 +1,121 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use crate::prelude::*;
+use crate::query::QueryResult;
+
+/// The cached state of a [`Query`]: the archetypes matching it. New archetypes are checked
+/// incrementally, so a query doesn't look at every archetype each time it runs.
+///
+/// A state must only be used with the [`World`] it was created for, using it with another world
+/// panics.
+pub struct QueryState<Q: WorldQuery, F: QueryFilter = ()> {
+    world_id: WorldId,
+    matched_archetypes: Vec<ArchetypeId>,
+    /// How many of the world's archetypes were already checked.
+    archetype_generation: usize,
+    /// The components accessed by the query and its filter.
+    access_table: AccessTable,
+    _marker: PhantomData<fn() -> (Q, F)>,
+}
+
+impl<Q: WorldQuery, F: QueryFilter> QueryState<Q, F> {
+    pub fn new(world: &World) -> Self {
+        let mut access_table = Q::access_table();
+        access_table.extend(F::access_table());
+        let mut state = Self {
+            world_id: world.id(),
+            matched_archetypes: Vec::new(),
+            archetype_generation: 0,
+            access_table,
+            _marker: PhantomData,
+        };
+        state.update_archetypes(world);
+        state
+    }
+
+    /// Check the archetypes created since the last update.
+    pub fn update_archetypes(&mut self, world: &World) {
+        self.validate_world(world);
+        for archetype in world.archetypes().iter().skip(self.archetype_generation) {
+            self.archetype_generation += 1;
+            if Q::matches_archetype(archetype) && F::matches_archetype(archetype) {
+                self.matched_archetypes.push(archetype.id());
+            }
+        }
+    }
+
+    /// The archetypes matching the query, some may be empty.
+    pub fn matched_archetypes(&self) -> &[ArchetypeId] {
+        &self.matched_archetypes
+    }
+
+    pub fn access_table(&self) -> &AccessTable {
+        &self.access_table
+    }
+
+    /// A [`Query`] of the world, detecting changes since the world's last update. Only for
+    /// read-only queries, see [`QueryState::query_mut`] for the others.
+    pub fn query<'w>(&'w mut self, world: &'w World) -> Query<'w, Q, F>
+    where
+        Q: ReadOnlyWorldQuery,
+    {
+        self.update_archetypes(world);
+        self.query_unchecked(world, world.last_change_tick(), world.change_tick())
+    }
+
+    /// Like [`QueryState::query`], borrowing the world mutably so the data can be mutated.
+    pub fn query_mut<'w>(&'w mut self, world: &'w mut World) -> Query<'w, Q, F> {
+        self.update_archetypes(world);
+        let (last_run, this_run) = (world.last_change_tick(), world.change_tick());
+        self.query_unchecked(world, last_run, this_run)
+    }
+
+    /// Like [`QueryState::query`], without checking for new archetypes.
+    pub(crate) fn query_unchecked<'w>(
+        &'w self,
+        world: &'w World,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Query<'w, Q, F> {
+        self.validate_world(world);
+        Query::new(
+            world,
+            Cow::Borrowed(&self.matched_archetypes),
+            last_run,
+            this_run,
+        )
+    }
+
+    /// The data of the entity, if it matches the query. Only for read-only queries, see
+    /// [`QueryState::get_mut`] for the others.
+    pub fn get<'w>(&'w mut self, world: &'w World, entity: Entity) -> QueryResult<Q::Data<'w>>
+    where
+        Q: ReadOnlyWorldQuery,
+    {
+        self.query(world).get_inner(entity)
+    }
+
+    /// Like [`QueryState::get`], borrowing the world mutably so the data can be mutated.
+    pub fn get_mut<'w>(
+        &'w mut self,
+        world: &'w mut World,
+        entity: Entity,
+    ) -> QueryResult<Q::Data<'w>> {
+        self.query_mut(world).get_inner(entity)
+    }
+
+    fn validate_world(&self, world: &World) {
+        assert_eq!(
+            self.world_id,
+            world.id(),
+            "The QueryState of {} was created for another World",
+            std::any::type_name::<Q>()
+        );
+    }
+
+    /// Take the matched archetypes, for a query that doesn't keep the state.
+    pub(crate) fn into_matched_archetypes(self) -> Vec<ArchetypeId> {
+        self.matched_archetypes
+    }
+}
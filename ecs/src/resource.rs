@@ -3,6 +3,7 @@ mod table;
 
 pub(crate) use table::ResTable;
 
+use crate::change_detection::{ResourceTicks, Tick};
 use std::any::{Any, TypeId};
 use std::marker::PhantomData;
 use std::sync::{RwLockReadGuard, RwLockWriteGuard};
@@ -10,12 +11,19 @@ use std::sync::{RwLockReadGuard, RwLockWriteGuard};
 /// Smart pointer for accessing a resource in a shared manner.
 pub struct Res<'r, R: Resource> {
     lock: RwLockReadGuard<'r, dyn Resource>,
+    ticks: &'r ResourceTicks,
+    last_run: Tick,
+    this_run: Tick,
     _marker: PhantomData<R>,
 }
 
 /// Smart pointer for accessing a resource in an exclusive manner.
+/// Mutably dereferencing it marks the resource as changed.
 pub struct ResMut<'r, R: Resource> {
     lock: RwLockWriteGuard<'r, dyn Resource>,
+    ticks: &'r ResourceTicks,
+    last_run: Tick,
+    this_run: Tick,
     _marker: PhantomData<R>,
 }
 
@@ -18,6 +18,49 @@ impl<'r, R: Resource> std::ops::Deref for ResMut<'r, R> {
 
 impl<'r, R: Resource> std::ops::DerefMut for ResMut<'r, R> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        downcast_res_mut(self.lock.deref_mut()).unwrap()
+        self.set_changed();
+        self.bypass_change_detection()
+    }
+}
+
+impl<'r, R: Resource> Res<'r, R> {
+    /// Check if the resource was inserted since the system last ran.
+    pub fn is_added(&self) -> bool {
+        self.ticks
+            .added()
+            .is_newer_than(self.last_run, self.this_run)
+    }
+
+    /// Check if the resource was changed (or inserted) since the system last ran.
+    pub fn is_changed(&self) -> bool {
+        self.ticks
+            .changed()
+            .is_newer_than(self.last_run, self.this_run)
+    }
+}
+
+impl<'r, R: Resource> ResMut<'r, R> {
+    /// Check if the resource was inserted since the system last ran.
+    pub fn is_added(&self) -> bool {
+        self.ticks
+            .added()
+            .is_newer_than(self.last_run, self.this_run)
+    }
+
+    /// Check if the resource was changed (or inserted) since the system last ran.
+    pub fn is_changed(&self) -> bool {
+        self.ticks
+            .changed()
+            .is_newer_than(self.last_run, self.this_run)
+    }
+
+    /// Mark the resource as changed, without mutating it.
+    pub fn set_changed(&mut self) {
+        self.ticks.set_changed(self.this_run);
+    }
+
+    /// Get exclusive access to the resource without marking it as changed.
+    pub fn bypass_change_detection(&mut self) -> &mut R {
+        downcast_res_mut(std::ops::DerefMut::deref_mut(&mut self.lock)).unwrap()
     }
 }
@@ -1,5 +1,6 @@
 use super::{downcast_res_mut, get_res_id, res_id, Resource, ResourceId};
 use super::{Res, ResMut};
+use crate::change_detection::{ResourceTicks, Tick};
 use hashbrown::hash_map::HashMap;
 use std::marker::PhantomData;
 use std::sync::{Arc, RwLock};
@@ -17,10 +18,16 @@ pub type ResQueryResult<'r, R> = Result<Res<'r, R>, ResQueryError>;
 /// The result of querying the resource table for an [`exclusive access smart pointer to a resource`](`ResMut`)
 pub type ResMutQueryResult<'r, R> = Result<ResMut<'r, R>, ResQueryError>;
 
+/// A resource, along with when it was added and last changed.
+pub(crate) struct ResourceCell<R: ?Sized> {
+    ticks: ResourceTicks,
+    lock: RwLock<R>,
+}
+
 /// This table maps a [`ResourceId`] to a refrence counted smart pointer to it.
 #[derive(Clone)]
 pub(crate) struct ResTable {
-    map: HashMap<ResourceId, Arc<RwLock<dyn Resource>>>,
+    map: HashMap<ResourceId, Arc<ResourceCell<dyn Resource>>>,
     err_when_poisoned: bool,
 }
 
@@ -35,46 +42,83 @@ impl Default for ResTable {
 
 impl ResTable {
     /// Insert a [`Resource`] into the global resource table.
-    pub(crate) fn insert_resource<R: Resource>(&mut self, res: R) {
-        self.map
-            .insert(get_res_id(&res), Arc::new(RwLock::new(res)));
+    /// The resource is marked as added (and changed) at `change_tick`.
+    pub(crate) fn insert_resource<R: Resource>(&mut self, res: R, change_tick: Tick) {
+        self.map.insert(
+            get_res_id(&res),
+            Arc::new(ResourceCell {
+                ticks: ResourceTicks::new(change_tick),
+                lock: RwLock::new(res),
+            }),
+        );
+    }
+
+    /// Clamp the ticks of every resource, see [`Tick::check_tick`].
+    pub(crate) fn check_change_ticks(&self, this_run: Tick) {
+        for res in self.map.values() {
+            res.ticks.check_ticks(this_run);
+        }
+    }
+
+    /// Check that the [`Resource`] exists, and isn't poisoned if poisoned resources are errors.
+    pub(crate) fn check_resource<R: Resource>(&self) -> Result<(), ResQueryError> {
+        self.get_res_entry::<R>().map(|_| ())
     }
 
     /// Get a [`Res`] smart pointer (shared accesss) to a [`Resource`].
-    pub(crate) fn get_resource<R: Resource>(&self) -> ResQueryResult<R> {
+    /// Changes are detected relative to `last_run`, as seen from `this_run`.
+    pub(crate) fn get_resource<R: Resource>(
+        &self,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> ResQueryResult<R> {
         self.get_res_entry::<R>().map(|res| Res {
-            lock: res.read().map_or_else(|e| e.into_inner(), |guard| guard),
+            lock: res
+                .lock
+                .read()
+                .map_or_else(|e| e.into_inner(), |guard| guard),
+            ticks: &res.ticks,
+            last_run,
+            this_run,
             _marker: PhantomData,
         })
     }
 
     /// Get a [`ResMut`] smart pointer (exclusive access) to a [`Resource`].
-    pub(crate) fn get_resource_mut<R: Resource>(&self) -> ResMutQueryResult<R> {
+    /// Mutating the resource through it marks the resource as changed at `this_run`.
+    pub(crate) fn get_resource_mut<R: Resource>(
+        &self,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> ResMutQueryResult<R> {
         self.get_res_entry::<R>().map(|res| ResMut {
-            lock: res.write().map_or_else(|e| e.into_inner(), |guard| guard),
+            lock: res
+                .lock
+                .write()
+                .map_or_else(|e| e.into_inner(), |guard| guard),
+            ticks: &res.ticks,
+            last_run,
+            this_run,
             _marker: PhantomData,
         })
     }
 
-    pub(crate) fn get_direct_resource_mut<R: Resource>(&mut self) -> Option<&mut R> {
-        Some(
-            downcast_res_mut(
-                Arc::get_mut(self.get_res_entry_mut::<R>().ok()?)?
-                    .get_mut()
-                    .ok()?,
-            )
-            .unwrap(),
-        )
-    }
-
-    pub(crate) fn _direct_resource_mut<R: Resource>(&mut self) -> &mut R {
-        self.get_direct_resource_mut().unwrap()
+    /// Get a direct exclusive reference to the resource, and mark it as changed at `change_tick`.
+    pub(crate) fn get_direct_resource_mut<R: Resource>(
+        &mut self,
+        change_tick: Tick,
+    ) -> Option<&mut R> {
+        let cell = Arc::get_mut(self.get_res_entry_mut::<R>().ok()?)?;
+        cell.ticks.set_changed(change_tick);
+        Some(downcast_res_mut(cell.lock.get_mut().ok()?).unwrap())
     }
 
-    fn get_res_entry<R: Resource>(&self) -> Result<&Arc<RwLock<dyn Resource>>, ResQueryError> {
+    fn get_res_entry<R: Resource>(
+        &self,
+    ) -> Result<&Arc<ResourceCell<dyn Resource>>, ResQueryError> {
         let res_id = res_id::<R>();
         if let Some(res) = self.map.get(&res_id) {
-            if res.is_poisoned() && self.err_when_poisoned {
+            if res.lock.is_poisoned() && self.err_when_poisoned {
                 return Result::Err(ResQueryError::Poisoned);
             }
             Ok(res)
@@ -85,10 +129,10 @@ impl ResTable {
 
     fn get_res_entry_mut<R: Resource>(
         &mut self,
-    ) -> Result<&mut Arc<RwLock<dyn Resource>>, ResQueryError> {
+    ) -> Result<&mut Arc<ResourceCell<dyn Resource>>, ResQueryError> {
         let res_id = res_id::<R>();
         if let Some(res) = self.map.get_mut(&res_id) {
-            if res.is_poisoned() && self.err_when_poisoned {
+            if res.lock.is_poisoned() && self.err_when_poisoned {
                 return Result::Err(ResQueryError::Poisoned);
             }
             Ok(res)
@@ -0,0 +1,112 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::prelude::*;
+
+/// The states of a finite state machine, usually an enum. Derive it with `#[derive(States)]`.
+///
+/// See [`World::add_state`].
+pub trait States: Clone + PartialEq + Eq + Hash + Debug + Send + Sync + 'static {}
+
+/// The current state of type `S`. It only changes when the [`StateTransition`] schedule applies
+/// the [`NextState`].
+#[derive(Resource, Debug)]
+pub struct State<S: States>(S);
+
+impl<S: States> State<S> {
+    pub fn get(&self) -> &S {
+        &self.0
+    }
+}
+
+impl<S: States> std::ops::Deref for State<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The state to go to in the next [`StateTransition`], if any.
+#[derive(Resource, Debug)]
+pub struct NextState<S: States>(pub Option<S>);
+
+impl<S: States> Default for NextState<S> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<S: States> NextState<S> {
+    pub fn set(&mut self, state: S) {
+        self.0 = Some(state);
+    }
+}
+
+/// The schedule that runs when entering the state.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnEnter<S: States>(pub S);
+
+/// The schedule that runs when leaving the state.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnExit<S: States>(pub S);
+
+/// The schedule that runs when going from the state `from` to the state `to`,
+/// between [`OnExit`] and [`OnEnter`].
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnTransition<S: States> {
+    pub from: S,
+    pub to: S,
+}
+
+/// Inserted once the [`OnEnter`] schedule of the initial state ran.
+#[derive(Resource)]
+struct EnteredInitialState<S: States>(PhantomData<S>);
+
+/// Enter the initial state, then apply the [`NextState`] if it is a different state.
+/// The state schedules without systems are skipped.
+fn apply_state_transition<S: States>(world: &mut World) {
+    if !world.contains_resource::<EnteredInitialState<S>>() {
+        world.insert_resource(EnteredInitialState::<S>(PhantomData));
+        let initial = world.get_resource::<State<S>>().unwrap().0.clone();
+        let _ = world.try_run_schedule(OnEnter(initial));
+    }
+
+    let Some(next) = world
+        .get_pure_resource_mut::<NextState<S>>()
+        .and_then(|next_state| next_state.0.take())
+    else {
+        return;
+    };
+    let current = world.get_resource::<State<S>>().unwrap().0.clone();
+    if next == current {
+        return;
+    }
+    let _ = world.try_run_schedule(OnExit(current.clone()));
+    world.get_pure_resource_mut::<State<S>>().unwrap().0 = next.clone();
+    let _ = world.try_run_schedule(OnTransition {
+        from: current,
+        to: next.clone(),
+    });
+    let _ = world.try_run_schedule(OnEnter(next));
+}
+
+impl World {
+    /// Add the [`State`] and [`NextState`] resources for the states `S`, starting in the default
+    /// state. Transitions are applied in the [`StateTransition`] schedule, between [`PreUpdate`]
+    /// and [`Update`], and run the [`OnExit`], [`OnTransition`] and [`OnEnter`] schedules.
+    pub fn add_state<S: States + Default>(&mut self) {
+        if self.contains_resource::<State<S>>() {
+            return;
+        }
+        self.insert_resource(State(S::default()));
+        self.init_resource::<NextState<S>>();
+        self.add_systems(StateTransition, apply_state_transition::<S>);
+    }
+}
+
+/// A [`Condition`] that is true while the current state is `state`.
+pub fn in_state<S: States>(state: S) -> impl FnMut(Res<State<S>>) -> bool + Clone + Send + Sync {
+    move |current: Res<State<S>>| current.0 == state
+}
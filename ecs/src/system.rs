@@ -1,28 +1,55 @@
+pub(crate) mod commands;
+pub(crate) mod condition;
+pub(crate) mod config;
+pub(crate) mod error;
+pub(crate) mod executor;
 pub mod func_system;
+pub(crate) mod pipe;
 pub(crate) mod schedule;
 pub(crate) mod system_param;
+pub(crate) mod system_registry;
 pub(crate) mod system_set;
 
 use crate::prelude::{unsafe_world_cell::UnsafeWorldCell, *};
 use bevy_utils::all_tuples;
+pub use commands::*;
+pub use condition::{
+    on_event, resource_changed, resource_exists, BoxedCondition, Condition, IntoCondition,
+};
+pub use config::*;
+pub use error::*;
 pub use func_system::*;
+pub use pipe::*;
 pub use schedule::*;
-pub use system_param::SystemParam;
+pub use system_param::{AccessToWorld, Local, SystemParam};
+pub use system_registry::{RegisteredSystemError, SystemId};
 pub use system_set::*;
 
-/// The trait for all systems.
-pub trait System {
+/// The trait for all systems. Systems may run on other threads (see [`World::run_schedule`]).
+pub trait System: Send + Sync {
     /// The name of the system.
     fn name(&self) -> &'static str;
 
     /// Run using an [`UnsafeWorldCell`] as opposed to a regular exclusive reference.
-    unsafe fn run_unsafe(&mut self, world: UnsafeWorldCell<'_>);
+    /// The system must be [initialized](System::initialize) first.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure nothing running at the same time accesses the world in a way
+    /// that conflicts with the [`access_table`](System::access_table) and
+    /// [`access_to_whole_world`](System::access_to_whole_world) of the system.
+    unsafe fn run_unsafe(&mut self, world: UnsafeWorldCell<'_>) -> Result<(), SystemError>;
 
-    fn run(&mut self, world: &mut World) {
+    /// Create the state of the system's params, if it wasn't created yet.
+    fn initialize(&mut self, world: &mut World);
+
+    /// Run the system, returning its error instead of handing it to the world's [`ErrorHandler`].
+    fn run(&mut self, world: &mut World) -> Result<(), SystemError> {
+        self.initialize(world);
         let uwc = UnsafeWorldCell::from_world(world);
         // SAFETY: We have mutable access to the world, so we can safely create an UnsafeWorldCell
         // and run the system with it.
-        unsafe { self.run_unsafe(uwc) };
+        unsafe { self.run_unsafe(uwc) }
     }
 
     /// Check for access conflicts within the system's parameters.
@@ -30,6 +57,35 @@ pub trait System {
 
     /// Which of the [`World`]'s resources are [`Access`]ed by the system.
     fn access_table(&self) -> AccessTable;
+
+    /// How the system accesses the [`World`] as a whole, systems that write to it run exclusively.
+    fn access_to_whole_world(&self) -> AccessToWorld;
+
+    /// True if the system records [`Commands`], the systems after it in the schedule
+    /// only run once the commands are applied.
+    fn is_deferred(&self) -> bool;
+
+    /// Clamp the tick of the system's last run, see [`World::check_change_ticks`].
+    fn check_change_tick(&mut self, this_run: Tick);
+
+    /// Check if the system can't run at the same time as `other`.
+    fn conflicts_with(&self, other: &dyn System) -> bool {
+        match (self.access_to_whole_world(), other.access_to_whole_world()) {
+            (AccessToWorld::NoAccess, AccessToWorld::NoAccess) => {
+                self.access_table().conflicts_with(&other.access_table())
+            }
+            (AccessToWorld::Access(Access::Read), AccessToWorld::Access(Access::Read)) => false,
+            (AccessToWorld::Access(Access::Read), AccessToWorld::NoAccess) => other
+                .access_table()
+                .values()
+                .any(|access| *access == Access::Write),
+            (AccessToWorld::NoAccess, AccessToWorld::Access(Access::Read)) => self
+                .access_table()
+                .values()
+                .any(|access| *access == Access::Write),
+            _ => true,
+        }
+    }
 }
 
 pub type BoxedSystem = Box<dyn System>;
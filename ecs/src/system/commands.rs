@@ -0,0 +1,182 @@
+use crate::prelude::*;
+
+/// A structural change to the [`World`], recorded by [`Commands`] and applied later.
+pub trait Command: Send + 'static {
+    fn apply(self, world: &mut World);
+}
+
+impl<F> Command for F
+where
+    F: FnOnce(&mut World) + Send + 'static,
+{
+    fn apply(self, world: &mut World) {
+        self(world)
+    }
+}
+
+type BoxedCommand = Box<dyn FnOnce(&mut World) + Send>;
+
+/// A list of [`Command`]s, applied in the order they were pushed.
+#[derive(Default)]
+pub struct CommandQueue {
+    commands: Vec<BoxedCommand>,
+}
+
+impl CommandQueue {
+    pub fn push(&mut self, command: impl Command) {
+        self.commands.push(Box::new(|world| command.apply(world)));
+    }
+
+    /// Move all the commands of `other` to the end of this queue.
+    pub fn append(&mut self, other: &mut CommandQueue) {
+        self.commands.append(&mut other.commands);
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Apply (and remove) all the commands, after making the reserved entities alive.
+    pub fn apply(&mut self, world: &mut World) {
+        world.flush_entities();
+        for command in self.commands.drain(..) {
+            command(world);
+        }
+    }
+}
+
+/// A [`SystemParam`] that records structural changes to the [`World`]. They are applied after
+/// the system runs (see [`World::apply_commands`]), so it doesn't need access to the whole world.
+pub struct Commands<'w> {
+    queue: CommandQueue,
+    world: &'w World,
+}
+
+impl<'w> Commands<'w> {
+    pub fn new(world: &'w World) -> Self {
+        Self {
+            queue: CommandQueue::default(),
+            world,
+        }
+    }
+
+    /// Reserve an [`Entity`] that will be spawned (without components) when the commands are applied.
+    pub fn spawn_empty(&mut self) -> EntityCommands<'_, 'w> {
+        let entity = self.world.entities.reserve();
+        EntityCommands {
+            entity,
+            commands: self,
+        }
+    }
+
+    /// Reserve an [`Entity`] that will be spawned with the bundle when the commands are applied.
+    pub fn spawn<B: Bundle + Send + 'static>(&mut self, bundle: B) -> EntityCommands<'_, 'w> {
+        let mut entity_commands = self.spawn_empty();
+        entity_commands.insert(bundle);
+        entity_commands
+    }
+
+    /// Record commands for an existing [`Entity`].
+    pub fn entity(&mut self, entity: Entity) -> EntityCommands<'_, 'w> {
+        EntityCommands {
+            entity,
+            commands: self,
+        }
+    }
+
+    pub fn insert_resource<R: Resource>(&mut self, res: R) {
+        self.add(move |world: &mut World| world.insert_resource(res));
+    }
+
+    pub fn init_resource<R: Resource + Default>(&mut self) {
+        self.add(|world: &mut World| world.init_resource::<R>());
+    }
+
+    /// Record a custom [`Command`].
+    pub fn add(&mut self, command: impl Command) {
+        self.queue.push(command);
+    }
+}
+
+impl<'w> Drop for Commands<'w> {
+    fn drop(&mut self) {
+        self.world
+            .command_queue
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .append(&mut self.queue);
+    }
+}
+
+/// Records commands for a single [`Entity`].
+pub struct EntityCommands<'a, 'w> {
+    entity: Entity,
+    commands: &'a mut Commands<'w>,
+}
+
+impl<'a, 'w> EntityCommands<'a, 'w> {
+    pub fn id(&self) -> Entity {
+        self.entity
+    }
+
+    /// Insert the [`Bundle`] to the entity. Panics when applied if the entity doesn't exist.
+    pub fn insert<B: Bundle + Send + 'static>(&mut self, bundle: B) -> &mut Self {
+        let entity = self.entity;
+        self.commands.add(move |world: &mut World| {
+            world.entity(entity).insert(bundle);
+        });
+        self
+    }
+
+    /// Remove (and drop) the components of the [`Bundle`] from the entity, if it still exists.
+    pub fn remove<B: Bundle + 'static>(&mut self) -> &mut Self {
+        let entity = self.entity;
+        self.commands.add(move |world: &mut World| {
+            if world.contains_entity(entity) {
+                world.entity(entity).remove::<B>();
+            }
+        });
+        self
+    }
+
+    /// Despawn the entity, if it still exists.
+    pub fn despawn(&mut self) {
+        let entity = self.entity;
+        self.commands.add(move |world: &mut World| {
+            world.despawn(entity);
+        });
+    }
+
+    pub fn commands(&mut self) -> &mut Commands<'w> {
+        self.commands
+    }
+}
+
+unsafe impl SystemParam for Commands<'_> {
+    type Item<'a> = Commands<'a>;
+
+    type State = ();
+
+    fn init_state(_world: &mut World) -> Self::State {}
+
+    unsafe fn fetch_from_world<'a>(
+        _state: &'a mut Self::State,
+        world: UnsafeWorldCell<'a>,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) -> Self::Item<'a> {
+        Commands::new(world.world())
+    }
+
+    fn access_table() -> AccessTable {
+        AccessTable::new()
+    }
+
+    fn is_deferred() -> bool {
+        true
+    }
+}
@@ -0,0 +1,145 @@
+use crate::prelude::*;
+
+/// A read-only system returning `bool`, that decides if a system runs (see [`IntoSystemSet::run_if`]).
+pub trait Condition: Send + Sync {
+    /// The name of the condition.
+    fn name(&self) -> &'static str;
+
+    /// Run the condition using an [`UnsafeWorldCell`]. It must be initialized first.
+    /// Fails if one of its params can't be fetched.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure nothing running at the same time accesses the world in a way
+    /// that conflicts with the [`access_table`](Condition::access_table) and
+    /// [`access_to_whole_world`](Condition::access_to_whole_world) of the condition.
+    unsafe fn evaluate(&mut self, world: UnsafeWorldCell<'_>) -> Result<bool, SystemError>;
+
+    /// Create the state of the condition's params, if it wasn't created yet.
+    fn initialize(&mut self, world: &mut World);
+
+    /// Which of the [`World`]'s resources are read by the condition.
+    fn access_table(&self) -> AccessTable;
+
+    /// How the condition accesses the [`World`] as a whole.
+    fn access_to_whole_world(&self) -> AccessToWorld;
+
+    /// Clamp the tick of the condition's last run, see [`World::check_change_ticks`].
+    fn check_change_tick(&mut self, this_run: Tick);
+}
+
+pub type BoxedCondition = Box<dyn Condition>;
+
+pub trait IntoCondition<Marker>: Sized {
+    /// The type of [`Condition`] that this instance converts into.
+    type Condition: Condition + 'static;
+
+    /// Turns this value into its corresponding [`Condition`].
+    fn into_condition(this: Self) -> Self::Condition;
+}
+
+/// Box the condition, panicking if it writes to the world.
+pub(crate) fn boxed_condition<M>(condition: impl IntoCondition<M>) -> BoxedCondition {
+    let condition = IntoCondition::into_condition(condition);
+    let read_only = !condition
+        .access_table()
+        .values()
+        .any(|access| *access == Access::Write)
+        && matches!(
+            condition.access_to_whole_world(),
+            AccessToWorld::NoAccess | AccessToWorld::Access(Access::Read)
+        );
+    if !read_only {
+        panic!("Run condition {} can only read the world", condition.name());
+    }
+    Box::new(condition)
+}
+
+/// A system that only runs when all its conditions are true.
+pub(crate) struct ConditionalSystem {
+    system: BoxedSystem,
+    conditions: Vec<BoxedCondition>,
+}
+
+impl ConditionalSystem {
+    pub(crate) fn new(system: BoxedSystem, conditions: Vec<BoxedCondition>) -> Self {
+        Self { system, conditions }
+    }
+}
+
+impl System for ConditionalSystem {
+    fn name(&self) -> &'static str {
+        self.system.name()
+    }
+
+    unsafe fn run_unsafe(&mut self, world: UnsafeWorldCell<'_>) -> Result<(), SystemError> {
+        // Every condition is evaluated, so they all see the same changes the next time.
+        let mut should_run = true;
+        for condition in self.conditions.iter_mut() {
+            should_run &= condition.evaluate(world)?;
+        }
+        // The system's params are only fetched once the conditions passed, so a condition like
+        // `resource_exists` protects the system from fetching a missing resource.
+        if should_run {
+            self.system.run_unsafe(world)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.system.initialize(world);
+        for condition in self.conditions.iter_mut() {
+            condition.initialize(world);
+        }
+    }
+
+    fn check_conflict(&self) -> bool {
+        self.system.check_conflict()
+    }
+
+    fn access_table(&self) -> AccessTable {
+        let mut access_table = self.system.access_table();
+        for condition in &self.conditions {
+            access_table.extend(condition.access_table());
+        }
+        access_table
+    }
+
+    fn access_to_whole_world(&self) -> AccessToWorld {
+        let conditions_read_world = self
+            .conditions
+            .iter()
+            .any(|condition| condition.access_to_whole_world() != AccessToWorld::NoAccess);
+        match self.system.access_to_whole_world() {
+            AccessToWorld::NoAccess if conditions_read_world => AccessToWorld::Access(Access::Read),
+            access => access,
+        }
+    }
+
+    fn is_deferred(&self) -> bool {
+        self.system.is_deferred()
+    }
+
+    fn check_change_tick(&mut self, this_run: Tick) {
+        self.system.check_change_tick(this_run);
+        for condition in self.conditions.iter_mut() {
+            condition.check_change_tick(this_run);
+        }
+    }
+}
+
+/// A [`Condition`] that is true if the resource `R` exists.
+pub fn resource_exists<R: Resource>(res: Option<Res<R>>) -> bool {
+    res.is_some()
+}
+
+/// A [`Condition`] that is true if the resource `R` was added or changed since the condition last ran.
+pub fn resource_changed<R: Resource>(res: Option<Res<R>>) -> bool {
+    res.is_some_and(|res| res.is_changed())
+}
+
+/// A [`Condition`] that is true if events of type `T` were sent since the condition last ran.
+pub fn on_event<T: Event>(reader: EventReader<T>) -> bool {
+    !reader.is_empty()
+}
@@ -0,0 +1,138 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::condition::{boxed_condition, ConditionalSystem};
+use super::*;
+use crate::prelude::*;
+
+/// Identifies the systems that other systems can be ordered against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SystemLabel {
+    /// A named group of systems, see [`IntoSystemSet::in_set`].
+    Named(&'static str),
+    /// Every instance of a system, identified by the type of the system.
+    System(TypeId),
+    /// A single system of a chain, see [`IntoSystemSet::chain`].
+    Anonymous(usize),
+}
+
+impl SystemLabel {
+    /// The label of all the instances of the system `S`.
+    pub fn of_system<S: System + 'static>() -> Self {
+        SystemLabel::System(TypeId::of::<S>())
+    }
+
+    fn anonymous() -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        SystemLabel::Anonymous(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Values that can be turned into a [`SystemLabel`]: system set names and systems.
+pub trait IntoSystemLabel<Marker> {
+    fn into_label(self) -> SystemLabel;
+}
+
+impl IntoSystemLabel<()> for &'static str {
+    fn into_label(self) -> SystemLabel {
+        SystemLabel::Named(self)
+    }
+}
+
+impl IntoSystemLabel<()> for SystemLabel {
+    fn into_label(self) -> SystemLabel {
+        self
+    }
+}
+
+impl<M, I> IntoSystemLabel<(M,)> for I
+where
+    I: IntoSystem<M>,
+    I::System: 'static,
+{
+    fn into_label(self) -> SystemLabel {
+        SystemLabel::of_system::<I::System>()
+    }
+}
+
+/// A system, and where it should run relative to the other systems of its schedule.
+pub struct SystemConfig {
+    pub(crate) system: BoxedSystem,
+    /// The labels of the system, the first one is the label of the system's type.
+    pub(crate) labels: Vec<SystemLabel>,
+    pub(crate) before: Vec<SystemLabel>,
+    pub(crate) after: Vec<SystemLabel>,
+    /// The system only runs when all of them are true.
+    pub(crate) conditions: Vec<BoxedCondition>,
+}
+
+impl SystemConfig {
+    pub fn new<S: System + 'static>(system: Box<S>) -> Self {
+        Self {
+            system,
+            labels: vec![SystemLabel::of_system::<S>()],
+            before: Vec::new(),
+            after: Vec::new(),
+            conditions: Vec::new(),
+        }
+    }
+
+    /// The system, wrapped so it checks its run conditions first.
+    pub(crate) fn into_system(self) -> BoxedSystem {
+        if self.conditions.is_empty() {
+            self.system
+        } else {
+            Box::new(ConditionalSystem::new(self.system, self.conditions))
+        }
+    }
+}
+
+/// A group of [`SystemConfig`]s, returned by the ordering methods of [`IntoSystemSet`].
+pub struct SystemConfigs(pub(crate) Vec<SystemConfig>);
+
+impl SystemConfigs {
+    pub(crate) fn before(mut self, label: SystemLabel) -> Self {
+        for config in self.0.iter_mut() {
+            config.before.push(label);
+        }
+        self
+    }
+
+    pub(crate) fn after(mut self, label: SystemLabel) -> Self {
+        for config in self.0.iter_mut() {
+            config.after.push(label);
+        }
+        self
+    }
+
+    pub(crate) fn in_set(mut self, label: SystemLabel) -> Self {
+        for config in self.0.iter_mut() {
+            config.labels.push(label);
+        }
+        self
+    }
+
+    /// Give each system its own instance of the condition.
+    pub(crate) fn run_if<M>(mut self, condition: impl IntoCondition<M> + Clone) -> Self {
+        for config in self.0.iter_mut() {
+            config.conditions.push(boxed_condition(condition.clone()));
+        }
+        self
+    }
+
+    pub(crate) fn chain(mut self) -> Self {
+        let mut previous: Option<SystemLabel> = None;
+        for config in self.0.iter_mut() {
+            let label = SystemLabel::anonymous();
+            config.labels.push(label);
+            if let Some(previous) = previous {
+                config.after.push(previous);
+            }
+            previous = Some(label);
+        }
+        self
+    }
+
+    pub(crate) fn append(&mut self, mut other: SystemConfigs) {
+        self.0.append(&mut other.0);
+    }
+}
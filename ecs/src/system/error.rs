@@ -0,0 +1,123 @@
+use std::fmt::Display;
+
+use crate::prelude::*;
+
+/// Any error a fallible system can return.
+pub type BoxedError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Why the item of a [`SystemParam`] couldn't be fetched.
+#[derive(Debug)]
+pub enum ParamError {
+    /// The resource, named by its type, doesn't exist.
+    MissingResource(&'static str),
+    /// The resource, named by its type, was poisoned by a system that panicked.
+    PoisonedResource(&'static str),
+}
+
+impl Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamError::MissingResource(resource) => {
+                write!(f, "the resource {} doesn't exist", resource)
+            }
+            ParamError::PoisonedResource(resource) => {
+                write!(f, "the resource {} is poisoned", resource)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// The error of a system, handled by the [`World`]'s [`ErrorHandler`].
+#[derive(Debug)]
+pub enum SystemError {
+    /// The system didn't run, one of its params couldn't be fetched.
+    Param {
+        system: &'static str,
+        error: ParamError,
+    },
+    /// The system ran and returned an error.
+    Failed {
+        system: &'static str,
+        error: BoxedError,
+    },
+}
+
+impl SystemError {
+    /// The name of the system (or run condition) the error comes from.
+    pub fn system(&self) -> &'static str {
+        match self {
+            SystemError::Param { system, .. } | SystemError::Failed { system, .. } => system,
+        }
+    }
+}
+
+impl Display for SystemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SystemError::Param { system, error } => {
+                write!(f, "System {} couldn't run: {}", system, error)
+            }
+            SystemError::Failed { system, error } => {
+                write!(f, "System {} failed: {}", system, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SystemError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SystemError::Param { error, .. } => Some(error),
+            SystemError::Failed { error, .. } => Some(error.as_ref()),
+        }
+    }
+}
+
+/// The return type of a function that can be a system: `()`, or `Result<(), E>` for a fallible
+/// system whose errors go to the [`World`]'s [`ErrorHandler`].
+pub trait SystemOutput {
+    fn into_result(self) -> Result<(), BoxedError>;
+}
+
+impl SystemOutput for () {
+    fn into_result(self) -> Result<(), BoxedError> {
+        Ok(())
+    }
+}
+
+impl<E: Into<BoxedError>> SystemOutput for Result<(), E> {
+    fn into_result(self) -> Result<(), BoxedError> {
+        self.map_err(Into::into)
+    }
+}
+
+/// What the [`World`] does with the errors of its systems, see [`World::set_error_handler`].
+pub type ErrorHandler = fn(&mut World, SystemError);
+
+/// The default [`ErrorHandler`]: panic with the error.
+pub fn panic_on_error(_world: &mut World, error: SystemError) {
+    panic!("{}", error);
+}
+
+/// An [`ErrorHandler`] printing the error to stderr, and going on.
+pub fn log_error(_world: &mut World, error: SystemError) {
+    eprintln!("{}", error);
+}
+
+/// An [`ErrorHandler`] pushing the error into the [`Errors`] resource, added if it doesn't exist.
+pub fn collect_errors(world: &mut World, error: SystemError) {
+    if !world.contains_resource::<Errors>() {
+        world.init_resource::<Errors>();
+    }
+    world
+        .get_pure_resource_mut::<Errors>()
+        .expect("The Errors resource is poisoned")
+        .0
+        .push(error);
+}
+
+/// The errors collected by the [`collect_errors`] handler, oldest first.
+#[derive(Resource, Debug, Default)]
+pub struct Errors(pub Vec<SystemError>);
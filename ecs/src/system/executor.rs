@@ -0,0 +1,89 @@
+use std::sync::Mutex;
+
+use crate::prelude::*;
+
+/// For each system, the earlier systems of the schedule it has to wait for: the ones it
+/// [conflicts with](System::conflicts_with), the deferred ones whose commands it may depend on,
+/// and the ones it is explicitly ordered after (`dependencies`).
+pub(crate) fn conflict_graph(
+    systems: &[BoxedSystem],
+    dependencies: &[Vec<usize>],
+) -> Vec<Vec<usize>> {
+    systems
+        .iter()
+        .enumerate()
+        .map(|(index, system)| {
+            (0..index)
+                .filter(|&earlier| {
+                    systems[earlier].is_deferred()
+                        || dependencies[index].contains(&earlier)
+                        || systems[earlier].conflicts_with(system.as_ref())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Split the systems into waves, each system goes in the wave after the last system it waits for.
+/// The systems of a wave don't conflict with each other.
+fn waves(systems: &[BoxedSystem], dependencies: &[Vec<usize>]) -> Vec<usize> {
+    let mut wave_of: Vec<usize> = Vec::with_capacity(systems.len());
+    for waits_for in conflict_graph(systems, dependencies) {
+        let wave = waits_for
+            .into_iter()
+            .map(|earlier| wave_of[earlier] + 1)
+            .max()
+            .unwrap_or(0);
+        wave_of.push(wave);
+    }
+    wave_of
+}
+
+/// Run the systems wave by wave, running the systems of each wave concurrently on the thread pool.
+/// Commands are applied after every wave, then the errors of its systems are handed to the
+/// world's [`ErrorHandler`]. The systems must be sorted so each one comes after its
+/// `dependencies`.
+pub(crate) fn run_systems(
+    world: &mut World,
+    systems: &mut [BoxedSystem],
+    dependencies: &[Vec<usize>],
+) {
+    let wave_of = waves(systems, dependencies);
+    let wave_count = wave_of.iter().max().map_or(0, |last| last + 1);
+    for wave in 0..wave_count {
+        let mut wave_systems = systems
+            .iter_mut()
+            .zip(&wave_of)
+            .filter(|(_, system_wave)| **system_wave == wave)
+            .map(|(system, _)| system)
+            .collect::<Vec<_>>();
+
+        let errors = Mutex::new(Vec::new());
+        if let [system] = wave_systems.as_mut_slice() {
+            if let Err(error) = system.run(world) {
+                errors.lock().unwrap().push(error);
+            }
+        } else {
+            let world_cell = UnsafeWorldCell::from_world(world);
+            let errors = &errors;
+            rayon::scope(|scope| {
+                for system in wave_systems {
+                    // SAFETY: Each thread only accesses the world through the params its system
+                    // fetches from `world_cell`, plus the atomic change tick. The access tables of
+                    // systems of the same wave don't conflict, and systems that access the whole
+                    // world exclusively (`&mut World`) are always alone in their wave, so they run
+                    // on this thread through `System::run`.
+                    scope.spawn(move |_| {
+                        if let Err(error) = unsafe { system.run_unsafe(world_cell) } {
+                            errors.lock().unwrap().push(error);
+                        }
+                    });
+                }
+            });
+        }
+        world.apply_commands();
+        for error in errors.into_inner().unwrap() {
+            world.handle_error(error);
+        }
+    }
+}
@@ -6,8 +6,33 @@ use crate::prelude::*;
 
 pub trait SystemParamFunction<Marker>: Send + Sync + 'static {
     type Param: SystemParam;
+    /// The input of the function, passed as its first argument wrapped in [`In`]. `()` for
+    /// functions without input, only those can be [`System`]s or [`Condition`]s on their own.
+    type In;
+    /// The return type of the function, a [`SystemOutput`] for systems and `bool` for
+    /// [`Condition`]s.
+    type Out;
 
-    fn run(&mut self, world: &mut World, param: <Self::Param as SystemParam>::Item<'_>);
+    fn run(&mut self, input: Self::In, param: <Self::Param as SystemParam>::Item<'_>) -> Self::Out;
+}
+
+/// The input of a function system, the output of the system piped into it (see
+/// [`IntoPipeSystem::pipe`]). It must be the first argument of the function.
+#[derive(Debug)]
+pub struct In<T>(pub T);
+
+impl<T> std::ops::Deref for In<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for In<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
 }
 
 pub trait IntoSystem<Marker>: Sized {
@@ -18,12 +43,15 @@ pub trait IntoSystem<Marker>: Sized {
     fn into_system(this: Self) -> Self::System;
 }
 
-#[derive(Clone)]
 pub struct FunctionSystemStruct<Marker, F>
 where
     F: SystemParamFunction<Marker>,
 {
     func: F,
+    /// The state of the params, `None` until the system is initialized.
+    param_state: Option<<F::Param as SystemParam>::State>,
+    /// The world's change tick when the system last ran, changes are detected relative to it.
+    last_run: Tick,
     // NOTE: PhantomData<fn()-> T> gives this safe Send/Sync impls
     marker: PhantomData<fn() -> Marker>,
 }
@@ -31,65 +59,198 @@ where
 #[doc(hidden)]
 pub struct SPF; // Marks SystemParamFunction
 
-impl<Marker, F> System for FunctionSystemStruct<Marker, F>
+impl<Marker, F> FunctionSystemStruct<Marker, F>
 where
     F: SystemParamFunction<Marker>,
+{
+    pub(crate) fn new(func: F) -> Self {
+        Self {
+            func,
+            param_state: None,
+            last_run: Tick::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Fetch the params and call the function, unless a param can't be fetched.
+    /// The system must be initialized first.
+    pub(crate) unsafe fn call(
+        &mut self,
+        input: F::In,
+        world: UnsafeWorldCell<'_>,
+    ) -> Result<F::Out, SystemError> {
+        let param_state = self
+            .param_state
+            .as_mut()
+            .unwrap_or_else(|| panic!("System {} wasn't initialized", type_name::<F>()));
+        F::Param::validate(param_state, world).map_err(|error| SystemError::Param {
+            system: type_name::<F>(),
+            error,
+        })?;
+        let this_run = world.world().increment_change_tick();
+        let out = self.func.run(
+            input,
+            F::Param::fetch_from_world(param_state, world, self.last_run, this_run),
+        );
+        self.last_run = this_run;
+        Ok(out)
+    }
+
+    /// Clamp the tick of the last run, see [`World::check_change_ticks`].
+    pub(crate) fn check_last_run(&mut self, this_run: Tick) {
+        self.last_run.check_tick(this_run);
+    }
+
+    pub(crate) fn init_param_state(&mut self, world: &mut World) {
+        if self.param_state.is_none() {
+            self.param_state = Some(F::Param::init_state(world));
+        }
+    }
+}
+
+impl<Marker, F> System for FunctionSystemStruct<Marker, F>
+where
+    F: SystemParamFunction<Marker, In = ()>,
+    F::Out: SystemOutput,
 {
     fn name(&self) -> &'static str {
         type_name::<F>()
     }
 
-    unsafe fn run_unsafe(&mut self, world: UnsafeWorldCell<'_>) {
-        self.func
-            .run(world.world_mut(), F::Param::fetch_from_world(world));
+    unsafe fn run_unsafe(&mut self, world: UnsafeWorldCell<'_>) -> Result<(), SystemError> {
+        self.call((), world)?
+            .into_result()
+            .map_err(|error| SystemError::Failed {
+                system: type_name::<F>(),
+                error,
+            })
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.init_param_state(world);
     }
 
     fn access_table(&self) -> AccessTable {
         F::Param::access_table()
     }
 
+    fn access_to_whole_world(&self) -> AccessToWorld {
+        F::Param::access_to_whole_world()
+    }
+
+    fn is_deferred(&self) -> bool {
+        F::Param::is_deferred()
+    }
+
     fn check_conflict(&self) -> bool {
         self.access_table().is_conflicted() || F::Param::access_to_whole_world().is_conflicted()
     }
+
+    fn check_change_tick(&mut self, this_run: Tick) {
+        self.check_last_run(this_run);
+    }
+}
+
+impl<Marker, F> Condition for FunctionSystemStruct<Marker, F>
+where
+    F: SystemParamFunction<Marker, In = (), Out = bool>,
+{
+    fn name(&self) -> &'static str {
+        type_name::<F>()
+    }
+
+    unsafe fn evaluate(&mut self, world: UnsafeWorldCell<'_>) -> Result<bool, SystemError> {
+        self.call((), world)
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.init_param_state(world);
+    }
+
+    fn access_table(&self) -> AccessTable {
+        F::Param::access_table()
+    }
+
+    fn access_to_whole_world(&self) -> AccessToWorld {
+        F::Param::access_to_whole_world()
+    }
+
+    fn check_change_tick(&mut self, this_run: Tick) {
+        self.check_last_run(this_run);
+    }
+}
+
+impl<Marker: 'static, F> IntoCondition<(SPF, Marker)> for F
+where
+    F: SystemParamFunction<Marker, In = (), Out = bool>,
+{
+    type Condition = FunctionSystemStruct<Marker, F>;
+    fn into_condition(this: Self) -> Self::Condition {
+        FunctionSystemStruct::new(this)
+    }
 }
 
 impl<Marker, F> IntoSystem<(SPF, Marker)> for F
 where
-    F: SystemParamFunction<Marker>,
+    F: SystemParamFunction<Marker, In = ()>,
+    F::Out: SystemOutput,
 {
     type System = FunctionSystemStruct<Marker, F>;
     fn into_system(this: Self) -> Self::System {
-        FunctionSystemStruct {
-            func: this,
-            marker: PhantomData,
-        }
+        FunctionSystemStruct::new(this)
     }
 }
 
 macro_rules! impl_system_param_function {
     ($($param:ident),*) => {
         #[allow(unused, non_snake_case)]
-        impl<F: Send + Sync + 'static, $($param: SystemParam),*> SystemParamFunction<fn($($param),*)> for F
+        impl<Out, F: Send + Sync + 'static, $($param: SystemParam),*> SystemParamFunction<fn($($param),*) -> Out> for F
         where for <'a> &'a mut F:
-            FnMut($($param::Item<'_>),*) + FnMut($($param),*)
+            FnMut($($param::Item<'_>),*) -> Out + FnMut($($param),*) -> Out
         {
             type Param = ($($param),*);
+            type In = ();
+            type Out = Out;
 
-            fn run(&mut self, world: &mut World, param: <Self::Param as SystemParam>::Item<'_>) {
+            fn run(&mut self, _input: (), param: <Self::Param as SystemParam>::Item<'_>) -> Out {
                 // Yes, this is strange, but `rustc` fails to compile this impl
                 // without using this function. It fails to recognize that `func`
                 // is a function, potentially because of the multiple impls of `FnMut`
                 #[allow(clippy::too_many_arguments)]
-                fn call_inner<$($param,)*>(
-                    mut f: impl FnMut($($param,)*),
+                fn call_inner<Out, $($param,)*>(
+                    mut f: impl FnMut($($param,)*) -> Out,
                     $($param: $param,)*
-                ){
+                ) -> Out {
                     f($($param,)*)
                 }
                 let ($($param),*) = param;
                 call_inner(self, $($param),*)
             }
         }
+
+        // `In` isn't a `SystemParam`, so these don't overlap with the impls above.
+        #[allow(unused, non_snake_case)]
+        impl<Input, Out, F: Send + Sync + 'static, $($param: SystemParam),*> SystemParamFunction<fn(In<Input>, $($param),*) -> Out> for F
+        where for <'a> &'a mut F:
+            FnMut(In<Input>, $($param::Item<'_>),*) -> Out + FnMut(In<Input>, $($param),*) -> Out
+        {
+            type Param = ($($param),*);
+            type In = Input;
+            type Out = Out;
+
+            fn run(&mut self, input: Input, param: <Self::Param as SystemParam>::Item<'_>) -> Out {
+                #[allow(clippy::too_many_arguments)]
+                fn call_inner<Input, Out, $($param,)*>(
+                    mut f: impl FnMut(In<Input>, $($param,)*) -> Out,
+                    input: In<Input>,
+                    $($param: $param,)*
+                ) -> Out {
+                    f(input, $($param,)*)
+                }
+                let ($($param),*) = param;
+                call_inner(self, In(input), $($param),*)
+            }
+        }
     };
 }
 
@@ -0,0 +1,111 @@
+use crate::prelude::*;
+
+/// Feed the output of a function system into another one, see [`IntoPipeSystem::pipe`].
+pub trait IntoPipeSystem<MarkerA>: SystemParamFunction<MarkerA, In = ()> + Sized {
+    /// A system running `self`, then `other` with the output of `self` as its [`In`] argument.
+    /// Both share a single place in the schedule: the pipe has the accesses of both.
+    fn pipe<MarkerB, B>(self, other: B) -> PipeSystem<MarkerA, Self, MarkerB, B>
+    where
+        B: SystemParamFunction<MarkerB, In = Self::Out>,
+        B::Out: SystemOutput,
+    {
+        PipeSystem {
+            a: FunctionSystemStruct::new(self),
+            b: FunctionSystemStruct::new(other),
+        }
+    }
+}
+
+impl<MarkerA, A> IntoPipeSystem<MarkerA> for A where A: SystemParamFunction<MarkerA, In = ()> {}
+
+/// Two function systems, the output of the first one being the input of the second one.
+pub struct PipeSystem<MarkerA, A, MarkerB, B>
+where
+    A: SystemParamFunction<MarkerA>,
+    B: SystemParamFunction<MarkerB>,
+{
+    a: FunctionSystemStruct<MarkerA, A>,
+    b: FunctionSystemStruct<MarkerB, B>,
+}
+
+impl<MarkerA, A, MarkerB, B> System for PipeSystem<MarkerA, A, MarkerB, B>
+where
+    A: SystemParamFunction<MarkerA, In = ()>,
+    B: SystemParamFunction<MarkerB, In = A::Out>,
+    B::Out: SystemOutput,
+{
+    fn name(&self) -> &'static str {
+        type_name::<(A, B)>()
+    }
+
+    unsafe fn run_unsafe(&mut self, world: UnsafeWorldCell<'_>) -> Result<(), SystemError> {
+        let out = self.a.call((), world)?;
+        self.b
+            .call(out, world)?
+            .into_result()
+            .map_err(|error| SystemError::Failed {
+                system: self.name(),
+                error,
+            })
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.a.init_param_state(world);
+        self.b.init_param_state(world);
+    }
+
+    fn check_conflict(&self) -> bool {
+        // The two systems run one after the other, their accesses only conflict within each one.
+        A::Param::access_table().is_conflicted()
+            || A::Param::access_to_whole_world().is_conflicted()
+            || B::Param::access_table().is_conflicted()
+            || B::Param::access_to_whole_world().is_conflicted()
+    }
+
+    fn access_table(&self) -> AccessTable {
+        let mut access_table = A::Param::access_table();
+        access_table.extend(B::Param::access_table());
+        access_table
+    }
+
+    fn access_to_whole_world(&self) -> AccessToWorld {
+        match (
+            A::Param::access_to_whole_world(),
+            B::Param::access_to_whole_world(),
+        ) {
+            (AccessToWorld::NoAccess, access) | (access, AccessToWorld::NoAccess) => access,
+            (AccessToWorld::Access(Access::Read), AccessToWorld::Access(Access::Read)) => {
+                AccessToWorld::Access(Access::Read)
+            }
+            (AccessToWorld::ConflictingAccess, _) | (_, AccessToWorld::ConflictingAccess) => {
+                AccessToWorld::ConflictingAccess
+            }
+            _ => AccessToWorld::Access(Access::Write),
+        }
+    }
+
+    fn is_deferred(&self) -> bool {
+        A::Param::is_deferred() || B::Param::is_deferred()
+    }
+
+    fn check_change_tick(&mut self, this_run: Tick) {
+        self.a.check_last_run(this_run);
+        self.b.check_last_run(this_run);
+    }
+}
+
+#[doc(hidden)]
+pub struct PS; // Marks PipeSystem
+
+impl<MarkerA, A, MarkerB, B> IntoSystem<PS> for PipeSystem<MarkerA, A, MarkerB, B>
+where
+    A: SystemParamFunction<MarkerA, In = ()>,
+    B: SystemParamFunction<MarkerB, In = A::Out>,
+    B::Out: SystemOutput,
+{
+    type System = Self;
+
+    fn into_system(this: Self) -> Self::System {
+        this
+    }
+}
@@ -1,30 +1,446 @@
-use std::{fmt::Debug, hash::Hash};
+use std::any::Any;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
 
-use super::System;
+use super::condition::{boxed_condition, BoxedCondition, IntoCondition};
+use super::{executor, BoxedSystem, System, SystemConfigs, SystemLabel};
+use crate::prelude::{HashMap, Tick, UnsafeWorldCell, World};
+use derive::ScheduleLabel;
 
-pub trait ScheduleLabel {
-    const PLACE: usize;
+/// Identifies a [`Schedule`] of the [`World`]. Labels are values, derive the trait with
+/// `#[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash, Debug)]` on a struct or an enum.
+pub trait ScheduleLabel: Debug + Send + Sync + 'static {
+    fn dyn_clone(&self) -> BoxedScheduleLabel;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn dyn_eq(&self, other: &dyn ScheduleLabel) -> bool;
+
+    fn dyn_hash(&self, state: &mut dyn Hasher);
+}
+
+pub type BoxedScheduleLabel = Box<dyn ScheduleLabel>;
+
+impl PartialEq for dyn ScheduleLabel {
+    fn eq(&self, other: &Self) -> bool {
+        self.dyn_eq(other)
+    }
+}
+
+impl Eq for dyn ScheduleLabel {}
+
+impl Hash for dyn ScheduleLabel {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dyn_hash(state);
+    }
+}
+
+impl Clone for BoxedScheduleLabel {
+    fn clone(&self) -> Self {
+        self.dyn_clone()
+    }
 }
 
+/// Runs once, before the first [`PreUpdate`].
+#[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Startup;
 
+/// Runs first in every update, it advances the [`Time`](crate::prelude::Time).
+#[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct First;
+
+#[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct PreUpdate;
 
+/// Applies the [`NextState`](crate::prelude::NextState) of every state added with
+/// [`World::add_state`], between [`PreUpdate`] and [`Update`].
+#[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct StateTransition;
+
+/// Runs the [`FixedUpdate`] schedule as many times as needed, between [`StateTransition`] and
+/// [`Update`] (see [`World::init_time`]).
+#[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct RunFixedUpdateLoop;
+
+/// Runs zero or more times per update, once for each step of the
+/// [`Time<Fixed>`](crate::prelude::Fixed) clock accumulated since the last update.
+#[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FixedUpdate;
+
+#[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Update;
 
+#[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct PostUpdate;
 
-impl ScheduleLabel for Startup {
-    const PLACE: usize = 0;
+/// Returned when running a schedule that was never configured.
+#[derive(Debug)]
+pub struct ScheduleNotConfigured(pub BoxedScheduleLabel);
+
+impl Display for ScheduleNotConfigured {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The schedule {:?} was run but never configured, add systems to it or call \
+             World::init_schedule first",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ScheduleNotConfigured {}
+
+/// The schedules of the [`World`], and the order the main ones run in.
+///
+/// Every [`World::update`] runs the main schedules in order, the startup ones run before the
+/// first update. Schedules that aren't in either order (like a render schedule, or the state
+/// schedules) only run when they are run explicitly.
+pub struct Schedules {
+    schedules: HashMap<BoxedScheduleLabel, Schedule>,
+    startup_order: Vec<BoxedScheduleLabel>,
+    main_order: Vec<BoxedScheduleLabel>,
+}
+
+impl Default for Schedules {
+    fn default() -> Self {
+        Self {
+            schedules: HashMap::new(),
+            startup_order: vec![Box::new(Startup)],
+            main_order: vec![
+                Box::new(First),
+                Box::new(PreUpdate),
+                Box::new(StateTransition),
+                Box::new(RunFixedUpdateLoop),
+                Box::new(Update),
+                Box::new(PostUpdate),
+            ],
+        }
+    }
 }
-impl ScheduleLabel for PreUpdate {
-    const PLACE: usize = 250;
+
+impl Schedules {
+    pub fn contains(&self, label: &dyn ScheduleLabel) -> bool {
+        self.schedules.contains_key(label)
+    }
+
+    pub fn get(&self, label: &dyn ScheduleLabel) -> Option<&Schedule> {
+        self.schedules.get(label)
+    }
+
+    pub fn get_mut(&mut self, label: &dyn ScheduleLabel) -> Option<&mut Schedule> {
+        self.schedules.get_mut(label)
+    }
+
+    /// The schedule of the label, created empty if it wasn't configured yet.
+    pub fn entry(&mut self, label: &dyn ScheduleLabel) -> &mut Schedule {
+        if !self.contains(label) {
+            self.schedules
+                .insert(label.dyn_clone(), Schedule::default());
+        }
+        self.schedules.get_mut(label).unwrap()
+    }
+
+    /// The labels of the configured schedules.
+    pub fn labels(&self) -> impl Iterator<Item = &dyn ScheduleLabel> {
+        self.schedules.keys().map(|label| label.as_ref())
+    }
+
+    pub fn startup_order(&self) -> &[BoxedScheduleLabel] {
+        &self.startup_order
+    }
+
+    pub fn main_order(&self) -> &[BoxedScheduleLabel] {
+        &self.main_order
+    }
+
+    /// Run the schedule of `label` right after the main schedule `after`.
+    pub fn insert_after(&mut self, after: impl ScheduleLabel, label: impl ScheduleLabel) {
+        self.check_unordered(&label);
+        let index = Self::index_in(&self.main_order, &after) + 1;
+        self.main_order.insert(index, Box::new(label));
+    }
+
+    /// Run the schedule of `label` right before the main schedule `before`.
+    pub fn insert_before(&mut self, before: impl ScheduleLabel, label: impl ScheduleLabel) {
+        self.check_unordered(&label);
+        let index = Self::index_in(&self.main_order, &before);
+        self.main_order.insert(index, Box::new(label));
+    }
+
+    /// Run the schedule of `label` once, right after the startup schedule `after`.
+    pub fn insert_startup_after(&mut self, after: impl ScheduleLabel, label: impl ScheduleLabel) {
+        self.check_unordered(&label);
+        let index = Self::index_in(&self.startup_order, &after) + 1;
+        self.startup_order.insert(index, Box::new(label));
+    }
+
+    /// Run the schedule of `label` once, right before the startup schedule `before`.
+    pub fn insert_startup_before(&mut self, before: impl ScheduleLabel, label: impl ScheduleLabel) {
+        self.check_unordered(&label);
+        let index = Self::index_in(&self.startup_order, &before);
+        self.startup_order.insert(index, Box::new(label));
+    }
+
+    fn index_in(order: &[BoxedScheduleLabel], label: &dyn ScheduleLabel) -> usize {
+        order
+            .iter()
+            .position(|ordered| ordered.as_ref() == label)
+            .unwrap_or_else(|| panic!("The schedule {:?} isn't in the schedule order", label))
+    }
+
+    /// Panic if the label already runs as a startup or main schedule.
+    fn check_unordered(&self, label: &dyn ScheduleLabel) {
+        if self
+            .startup_order
+            .iter()
+            .chain(&self.main_order)
+            .any(|ordered| ordered.as_ref() == label)
+        {
+            panic!("The schedule {:?} is already in the schedule order", label);
+        }
+    }
+
+    /// Clamp the ticks of the last run of every system, see [`World::check_change_ticks`].
+    pub(crate) fn check_change_ticks(&mut self, this_run: Tick) {
+        for schedule in self.schedules.values_mut() {
+            schedule.check_change_ticks(this_run);
+        }
+    }
+
+    /// Take the schedule out, so it can run with an exclusive reference to the world.
+    pub(crate) fn remove(&mut self, label: &dyn ScheduleLabel) -> Option<Schedule> {
+        self.schedules.remove(label)
+    }
+
+    /// Put back a schedule taken out with [`Schedules::remove`], systems added to the label in the
+    /// meantime go after its systems.
+    pub(crate) fn put_back(&mut self, label: BoxedScheduleLabel, mut schedule: Schedule) {
+        if let Some(added) = self.schedules.remove(label.as_ref()) {
+            schedule.append(added);
+        }
+        self.schedules.insert(label, schedule);
+    }
 }
-impl ScheduleLabel for Update {
-    const PLACE: usize = 500;
+
+/// The systems of a schedule label, sorted by their ordering constraints before they run.
+#[derive(Default)]
+pub struct Schedule {
+    systems: Vec<BoxedSystem>,
+    /// The labels and ordering constraints of each system.
+    nodes: Vec<SystemNode>,
+    /// For each system, the systems it has to run after. `None` until the systems are sorted.
+    dependencies: Option<Vec<Vec<usize>>>,
+    /// The schedule only runs when all of them are true.
+    conditions: Vec<BoxedCondition>,
 }
-impl ScheduleLabel for PostUpdate {
-    const PLACE: usize = 750;
+
+struct SystemNode {
+    labels: Vec<SystemLabel>,
+    before: Vec<SystemLabel>,
+    after: Vec<SystemLabel>,
 }
 
-pub const SCHEDULE_MAX_PLACE: usize = 100;
+impl Schedule {
+    pub fn add_systems(&mut self, configs: SystemConfigs) {
+        for mut config in configs.0 {
+            self.nodes.push(SystemNode {
+                labels: std::mem::take(&mut config.labels),
+                before: std::mem::take(&mut config.before),
+                after: std::mem::take(&mut config.after),
+            });
+            self.systems.push(config.into_system());
+        }
+        self.dependencies = None;
+    }
+
+    /// Only run the schedule when the condition is true, see [`World::add_schedule_condition`].
+    pub fn add_condition<M>(&mut self, condition: impl IntoCondition<M>) {
+        self.conditions.push(boxed_condition(condition));
+    }
+
+    /// Move the systems and conditions of `other` to the end of this schedule.
+    pub(crate) fn append(&mut self, mut other: Schedule) {
+        self.systems.append(&mut other.systems);
+        self.nodes.append(&mut other.nodes);
+        self.conditions.append(&mut other.conditions);
+        self.dependencies = None;
+    }
+
+    pub fn len(&self) -> usize {
+        self.systems.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+
+    /// The systems of the schedule, in the order they were added (or sorted, once the schedule ran).
+    pub fn systems(&self) -> impl Iterator<Item = &dyn System> {
+        self.systems.iter().map(|system| system.as_ref())
+    }
+
+    /// Sort the systems topologically, keeping the order they were added in when they aren't
+    /// constrained. Panics if the constraints have a cycle.
+    pub(crate) fn sort(&mut self) {
+        if self.dependencies.is_some() {
+            return;
+        }
+        let edges = self.edges();
+        let mut in_degree = vec![0; self.len()];
+        for to in edges.iter().flatten() {
+            in_degree[*to] += 1;
+        }
+
+        let mut ready = (0..self.len())
+            .filter(|&index| in_degree[index] == 0)
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(Reverse(index)) = ready.pop() {
+            order.push(index);
+            for &to in &edges[index] {
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    ready.push(Reverse(to));
+                }
+            }
+        }
+        if order.len() != self.len() {
+            panic!(
+                "The ordering of the systems has a cycle: {}",
+                self.find_cycle(&edges, &in_degree)
+            );
+        }
+
+        let mut new_index = vec![0; self.len()];
+        for (new, old) in order.iter().enumerate() {
+            new_index[*old] = new;
+        }
+        let mut dependencies = vec![Vec::new(); self.len()];
+        for (from, tos) in edges.iter().enumerate() {
+            for to in tos {
+                dependencies[new_index[*to]].push(new_index[from]);
+            }
+        }
+
+        let mut systems = std::mem::take(&mut self.systems)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        let mut nodes = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        for old in order {
+            self.systems.push(systems[old].take().unwrap());
+            self.nodes.push(nodes[old].take().unwrap());
+        }
+        self.dependencies = Some(dependencies);
+    }
+
+    /// Sort and initialize the systems, and run them (see [`executor::run_systems`]).
+    pub(crate) fn run(&mut self, world: &mut World) {
+        if !self.evaluate_conditions(world) {
+            return;
+        }
+        self.sort();
+        for system in self.systems.iter_mut() {
+            system.initialize(world);
+        }
+        executor::run_systems(
+            world,
+            &mut self.systems,
+            self.dependencies.as_ref().unwrap(),
+        );
+    }
+
+    /// Clamp the ticks of the last run of the systems and conditions, see
+    /// [`World::check_change_ticks`].
+    pub(crate) fn check_change_ticks(&mut self, this_run: Tick) {
+        for system in self.systems.iter_mut() {
+            system.check_change_tick(this_run);
+        }
+        for condition in self.conditions.iter_mut() {
+            condition.check_change_tick(this_run);
+        }
+    }
+
+    /// Evaluate every condition of the schedule, once, before any of its systems runs. A condition
+    /// that fails is handled by the world's error handler, and counts as false.
+    fn evaluate_conditions(&mut self, world: &mut World) -> bool {
+        let mut should_run = true;
+        for condition in self.conditions.iter_mut() {
+            condition.initialize(world);
+            // SAFETY: The world is borrowed exclusively, nothing else runs while the condition
+            // reads it.
+            match unsafe { condition.evaluate(UnsafeWorldCell::from_world(world)) } {
+                Ok(passed) => should_run &= passed,
+                Err(error) => {
+                    world.handle_error(error);
+                    should_run = false;
+                }
+            }
+        }
+        should_run
+    }
+
+    /// For each system, the systems that have to run after it.
+    fn edges(&self) -> Vec<Vec<usize>> {
+        let with_label = |label: SystemLabel| {
+            self.nodes
+                .iter()
+                .enumerate()
+                .filter(move |(_, node)| node.labels.contains(&label))
+                .map(|(index, _)| index)
+        };
+        let mut edges = vec![Vec::new(); self.len()];
+        for (index, node) in self.nodes.iter().enumerate() {
+            for label in &node.before {
+                edges[index].extend(with_label(*label).filter(|&other| other != index));
+            }
+            for label in &node.after {
+                for other in with_label(*label).filter(|&other| other != index) {
+                    edges[other].push(index);
+                }
+            }
+        }
+        for tos in edges.iter_mut() {
+            tos.sort();
+            tos.dedup();
+        }
+        edges
+    }
+
+    /// Walk the systems that couldn't be sorted back through their predecessors until one repeats,
+    /// and name the systems of the cycle. Every system left has a predecessor left, but not always
+    /// a successor: it can come after the cycle without being part of it.
+    fn find_cycle(&self, edges: &[Vec<usize>], in_degree: &[usize]) -> String {
+        let mut predecessors = vec![Vec::new(); self.len()];
+        for (from, tos) in edges.iter().enumerate() {
+            for &to in tos {
+                predecessors[to].push(from);
+            }
+        }
+        let mut path = vec![in_degree.iter().position(|degree| *degree > 0).unwrap()];
+        loop {
+            let last = *path.last().unwrap();
+            let previous = *predecessors[last]
+                .iter()
+                .find(|&&from| in_degree[from] > 0)
+                .unwrap();
+            if let Some(start) = path.iter().position(|&index| index == previous) {
+                path.drain(..start);
+                path.push(previous);
+                return path
+                    .iter()
+                    .rev()
+                    .map(|&index| self.systems[index].name())
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+            }
+            path.push(previous);
+        }
+    }
+}
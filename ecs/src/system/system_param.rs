@@ -16,13 +16,49 @@ pub unsafe trait SystemParam {
     /// The type of the item that will be passed to the system.
     type Item<'a>;
 
-    unsafe fn fetch_from_world(world: UnsafeWorldCell<'_>) -> Self::Item<'_>;
+    /// What the param keeps between the runs of its system. Every system has its own.
+    type State: Send + Sync + 'static;
+
+    /// Create the state, when the system is initialized against the world it runs on.
+    fn init_state(world: &mut World) -> Self::State;
+
+    /// Fetch the item, detecting changes since `last_run` and marking changes at `this_run`.
+    unsafe fn fetch_from_world<'a>(
+        state: &'a mut Self::State,
+        world: UnsafeWorldCell<'a>,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Item<'a>;
+
+    /// Check that the item can be fetched, before the system runs.
+    unsafe fn validate(
+        _state: &Self::State,
+        _world: UnsafeWorldCell<'_>,
+    ) -> Result<(), ParamError> {
+        Ok(())
+    }
 
     fn access_table() -> AccessTable;
 
     fn access_to_whole_world() -> AccessToWorld {
         AccessToWorld::NoAccess
     }
+
+    /// True if the param records changes that are applied after the system runs, like [`Commands`].
+    fn is_deferred() -> bool {
+        false
+    }
+}
+
+/// Check that the resource `R` can be fetched by [`Res`] or [`ResMut`].
+unsafe fn validate_resource<R: Resource>(world: UnsafeWorldCell<'_>) -> Result<(), ParamError> {
+    world
+        .world()
+        .check_resource::<R>()
+        .map_err(|error| match error {
+            ResQueryError::ResDoesntExist => ParamError::MissingResource(type_name::<R>()),
+            ResQueryError::Poisoned => ParamError::PoisonedResource(type_name::<R>()),
+        })
 }
 
 impl AccessToWorld {
@@ -31,11 +67,68 @@ impl AccessToWorld {
     }
 }
 
+/// A [`SystemParam`] for a value private to the system, kept between its runs. Every system
+/// has its own, starting from the default value.
+#[derive(Debug)]
+pub struct Local<'s, T: Default + Send + Sync + 'static>(&'s mut T);
+
+impl<T: Default + Send + Sync + 'static> std::ops::Deref for Local<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<T: Default + Send + Sync + 'static> std::ops::DerefMut for Local<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0
+    }
+}
+
+unsafe impl<T: Default + Send + Sync + 'static> SystemParam for Local<'_, T> {
+    type Item<'a> = Local<'a, T>;
+    type State = T;
+
+    fn init_state(_world: &mut World) -> Self::State {
+        T::default()
+    }
+
+    unsafe fn fetch_from_world<'a>(
+        state: &'a mut Self::State,
+        _world: UnsafeWorldCell<'a>,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) -> Self::Item<'a> {
+        Local(state)
+    }
+
+    fn access_table() -> AccessTable {
+        AccessTable::new()
+    }
+}
+
 unsafe impl<R: Resource> SystemParam for Res<'_, R> {
     type Item<'a> = Res<'a, R>;
 
-    unsafe fn fetch_from_world(world: UnsafeWorldCell<'_>) -> Self::Item<'_> {
-        world.get_resource::<R>().unwrap()
+    type State = ();
+
+    fn init_state(_world: &mut World) -> Self::State {}
+
+    unsafe fn fetch_from_world<'a>(
+        _state: &'a mut Self::State,
+        world: UnsafeWorldCell<'a>,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Item<'a> {
+        world
+            .world()
+            .get_resource_with_ticks::<R>(last_run, this_run)
+            .unwrap_or_else(|_| panic!("The resource {} can't be fetched", type_name::<R>()))
+    }
+
+    unsafe fn validate(_state: &Self::State, world: UnsafeWorldCell<'_>) -> Result<(), ParamError> {
+        validate_resource::<R>(world)
     }
 
     fn access_table() -> AccessTable {
@@ -48,8 +141,24 @@ unsafe impl<R: Resource> SystemParam for Res<'_, R> {
 unsafe impl<R: Resource> SystemParam for ResMut<'_, R> {
     type Item<'a> = ResMut<'a, R>;
 
-    unsafe fn fetch_from_world(world: UnsafeWorldCell<'_>) -> Self::Item<'_> {
-        world.get_resource_mut::<R>().expect(type_name::<R>())
+    type State = ();
+
+    fn init_state(_world: &mut World) -> Self::State {}
+
+    unsafe fn fetch_from_world<'a>(
+        _state: &'a mut Self::State,
+        world: UnsafeWorldCell<'a>,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Item<'a> {
+        world
+            .world()
+            .get_resource_mut_with_ticks::<R>(last_run, this_run)
+            .unwrap_or_else(|_| panic!("The resource {} can't be fetched", type_name::<R>()))
+    }
+
+    unsafe fn validate(_state: &Self::State, world: UnsafeWorldCell<'_>) -> Result<(), ParamError> {
+        validate_resource::<R>(world)
     }
 
     fn access_table() -> AccessTable {
@@ -59,22 +168,97 @@ unsafe impl<R: Resource> SystemParam for ResMut<'_, R> {
     }
 }
 
-unsafe impl<Q: WorldQuery> SystemParam for Query<'_, Q> {
-    type Item<'a> = Query<'a, Q>;
+/// Like [`Res`], but `None` instead of panicking if the resource doesn't exist.
+unsafe impl<R: Resource> SystemParam for Option<Res<'_, R>> {
+    type Item<'a> = Option<Res<'a, R>>;
+
+    type State = ();
+
+    fn init_state(_world: &mut World) -> Self::State {}
 
-    unsafe fn fetch_from_world(world: UnsafeWorldCell<'_>) -> Self::Item<'_> {
-        world.query::<Q>()
+    unsafe fn fetch_from_world<'a>(
+        _state: &'a mut Self::State,
+        world: UnsafeWorldCell<'a>,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Item<'a> {
+        world
+            .world()
+            .get_resource_with_ticks::<R>(last_run, this_run)
+            .ok()
     }
 
     fn access_table() -> AccessTable {
-        Q::access_table()
+        Res::<R>::access_table()
+    }
+}
+
+/// Like [`ResMut`], but `None` instead of panicking if the resource doesn't exist.
+unsafe impl<R: Resource> SystemParam for Option<ResMut<'_, R>> {
+    type Item<'a> = Option<ResMut<'a, R>>;
+
+    type State = ();
+
+    fn init_state(_world: &mut World) -> Self::State {}
+
+    unsafe fn fetch_from_world<'a>(
+        _state: &'a mut Self::State,
+        world: UnsafeWorldCell<'a>,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Item<'a> {
+        world
+            .world()
+            .get_resource_mut_with_ticks::<R>(last_run, this_run)
+            .ok()
+    }
+
+    fn access_table() -> AccessTable {
+        ResMut::<R>::access_table()
+    }
+}
+
+unsafe impl<Q: WorldQuery + 'static, F: QueryFilter + 'static> SystemParam for Query<'_, Q, F> {
+    type Item<'a> = Query<'a, Q, F>;
+
+    type State = QueryState<Q, F>;
+
+    fn init_state(world: &mut World) -> Self::State {
+        QueryState::new(world)
+    }
+
+    unsafe fn fetch_from_world<'a>(
+        state: &'a mut Self::State,
+        world: UnsafeWorldCell<'a>,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Item<'a> {
+        // Archetypes are only created with exclusive access to the world, so none can be
+        // created while the system runs.
+        state.update_archetypes(world.world());
+        state.query_unchecked(world.world(), last_run, this_run)
+    }
+
+    fn access_table() -> AccessTable {
+        let mut access_table = Q::access_table();
+        access_table.extend(F::access_table());
+        access_table
     }
 }
 
 unsafe impl SystemParam for &World {
     type Item<'a> = &'a World;
 
-    unsafe fn fetch_from_world(world: UnsafeWorldCell<'_>) -> Self::Item<'_> {
+    type State = ();
+
+    fn init_state(_world: &mut World) -> Self::State {}
+
+    unsafe fn fetch_from_world<'a>(
+        _state: &'a mut Self::State,
+        world: UnsafeWorldCell<'a>,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) -> Self::Item<'a> {
         unsafe { world.world() }
     }
 
@@ -90,7 +274,16 @@ unsafe impl SystemParam for &World {
 unsafe impl SystemParam for &mut World {
     type Item<'a> = &'a mut World;
 
-    unsafe fn fetch_from_world(world: UnsafeWorldCell<'_>) -> Self::Item<'_> {
+    type State = ();
+
+    fn init_state(_world: &mut World) -> Self::State {}
+
+    unsafe fn fetch_from_world<'a>(
+        _state: &'a mut Self::State,
+        world: UnsafeWorldCell<'a>,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) -> Self::Item<'a> {
         unsafe { world.world_mut() }
     }
 
@@ -108,9 +301,29 @@ macro_rules! impl_tuple_sys_param {
         #[allow(unused)]
         unsafe impl<$($param: SystemParam),*> SystemParam for ($($param,)*) {
             type Item<'a> = ($($param::Item<'a>,)*);
+            type State = ($($param::State,)*);
+
+            #[allow(clippy::unused_unit)]
+            fn init_state(world: &mut World) -> Self::State {
+                ($($param::init_state(world),)*)
+            }
 
-            unsafe fn fetch_from_world(world: UnsafeWorldCell<'_>) -> Self::Item<'_> {
-                ($($param::fetch_from_world(world),)*)
+            #[allow(non_snake_case, clippy::unused_unit)]
+            unsafe fn fetch_from_world<'a>(
+                state: &'a mut Self::State,
+                world: UnsafeWorldCell<'a>,
+                last_run: Tick,
+                this_run: Tick,
+            ) -> Self::Item<'a> {
+                let ($($param,)*) = state;
+                ($($param::fetch_from_world($param, world, last_run, this_run),)*)
+            }
+
+            #[allow(non_snake_case)]
+            unsafe fn validate(state: &Self::State, world: UnsafeWorldCell<'_>) -> Result<(), ParamError> {
+                let ($($param,)*) = state;
+                $($param::validate($param, world)?;)*
+                Ok(())
             }
 
             fn access_table() -> AccessTable {
@@ -134,6 +347,10 @@ macro_rules! impl_tuple_sys_param {
                 )*
                 access
             }
+
+            fn is_deferred() -> bool {
+                false $(|| $param::is_deferred())*
+            }
         }
     };
 }
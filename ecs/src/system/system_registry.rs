@@ -0,0 +1,107 @@
+use std::fmt::Display;
+
+use crate::prelude::*;
+
+/// The id of a system registered with [`World::register_system`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SystemId(usize);
+
+/// The systems registered in a [`World`], to run on demand.
+#[derive(Default)]
+pub(crate) struct RegisteredSystems {
+    /// `None` while the system runs.
+    systems: HashMap<SystemId, Option<BoxedSystem>>,
+    next_id: usize,
+}
+
+/// Why a registered system couldn't be run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisteredSystemError {
+    /// The system was never registered, or it was removed.
+    NotRegistered(SystemId),
+    /// The system is already running, it tried to run itself.
+    Recursive(SystemId),
+}
+
+impl Display for RegisteredSystemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegisteredSystemError::NotRegistered(id) => {
+                write!(f, "The system {:?} isn't registered", id)
+            }
+            RegisteredSystemError::Recursive(id) => {
+                write!(f, "The system {:?} tried to run itself", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegisteredSystemError {}
+
+impl RegisteredSystems {
+    /// Clamp the ticks of the last run of every system, see [`World::check_change_ticks`].
+    pub(crate) fn check_change_ticks(&mut self, this_run: Tick) {
+        for system in self.systems.values_mut().flatten() {
+            system.check_change_tick(this_run);
+        }
+    }
+}
+
+impl World {
+    /// Register the system, to run it with [`World::run_system_by_id`] or
+    /// [`Commands::run_system`]. Unlike [`World::run_system`], the system is kept between runs,
+    /// along with the state of its params (like its [`Local`]s).
+    pub fn register_system<M, I>(&mut self, sys: I) -> SystemId
+    where
+        I: IntoSystem<M>,
+        I::System: 'static,
+    {
+        let sys = IntoSystem::into_system(sys);
+        if sys.check_conflict() {
+            panic!("System {} has conflicting access", sys.name());
+        }
+        let registered = &mut self.registered_systems;
+        let id = SystemId(registered.next_id);
+        registered.next_id += 1;
+        registered.systems.insert(id, Some(Box::new(sys)));
+        id
+    }
+
+    /// Remove the registered system, returning it if it isn't running.
+    pub fn remove_system(&mut self, id: SystemId) -> Option<BoxedSystem> {
+        self.registered_systems.systems.remove(&id).flatten()
+    }
+
+    /// Run the registered system, and apply its commands. Its error goes to the [`ErrorHandler`].
+    pub fn run_system_by_id(&mut self, id: SystemId) -> Result<(), RegisteredSystemError> {
+        let mut sys = self
+            .registered_systems
+            .systems
+            .get_mut(&id)
+            .ok_or(RegisteredSystemError::NotRegistered(id))?
+            .take()
+            .ok_or(RegisteredSystemError::Recursive(id))?;
+        let result = sys.run(self);
+        self.apply_commands();
+        // The system may have been removed while it ran.
+        if let Some(slot) = self.registered_systems.systems.get_mut(&id) {
+            *slot = Some(sys);
+        }
+        if let Err(error) = result {
+            self.handle_error(error);
+        }
+        Ok(())
+    }
+}
+
+impl Commands<'_> {
+    /// Run the registered system when the commands are applied, see [`World::run_system_by_id`].
+    /// Panics when applied if the system can't be run.
+    pub fn run_system(&mut self, id: SystemId) {
+        self.add(move |world: &mut World| {
+            if let Err(err) = world.run_system_by_id(id) {
+                panic!("{}", err);
+            }
+        });
+    }
+}
@@ -3,12 +3,45 @@ use crate::prelude::*;
 
 pub trait SystemSet {
     fn systems(self) -> Vec<BoxedSystem>;
+
+    /// The systems, along with how they are ordered.
+    fn configs(self) -> SystemConfigs;
 }
 
-pub trait IntoSystemSet<Marker> {
+pub trait IntoSystemSet<Marker>: Sized {
     type SysSet: SystemSet;
 
     fn into_system_set(self) -> Self::SysSet;
+
+    fn into_configs(self) -> SystemConfigs {
+        self.into_system_set().configs()
+    }
+
+    /// Run the systems before the systems with the label (or the given system).
+    fn before<M>(self, label: impl IntoSystemLabel<M>) -> SystemConfigs {
+        self.into_configs().before(label.into_label())
+    }
+
+    /// Run the systems after the systems with the label (or the given system).
+    fn after<M>(self, label: impl IntoSystemLabel<M>) -> SystemConfigs {
+        self.into_configs().after(label.into_label())
+    }
+
+    /// Add the systems to a named set, so other systems can be ordered against all of them.
+    fn in_set(self, name: &'static str) -> SystemConfigs {
+        self.into_configs().in_set(SystemLabel::Named(name))
+    }
+
+    /// Only run the systems when the [`Condition`] is true. It is evaluated before the systems fetch
+    /// their params, and each system of the set evaluates its own copy of it.
+    fn run_if<M>(self, condition: impl IntoCondition<M> + Clone) -> SystemConfigs {
+        self.into_configs().run_if(condition)
+    }
+
+    /// Run the systems one after the other, in the order they are given.
+    fn chain(self) -> SystemConfigs {
+        self.into_configs().chain()
+    }
 }
 
 macro_rules! impl_system_set {
@@ -19,30 +52,58 @@ macro_rules! impl_system_set {
                 let ($($system),*) = self;
                 vec![$($system as BoxedSystem),*]
             }
+
+            fn configs(self) -> SystemConfigs {
+                let ($($system),*) = self;
+                SystemConfigs(vec![$(SystemConfig::new($system)),*])
+            }
         }
     }
 }
 
 all_tuples!(impl_system_set, 0, 15, S);
 
+impl SystemSet for SystemConfigs {
+    fn systems(self) -> Vec<BoxedSystem> {
+        self.0.into_iter().map(SystemConfig::into_system).collect()
+    }
+
+    fn configs(self) -> SystemConfigs {
+        self
+    }
+}
+
+#[doc(hidden)]
+pub struct SC; // Marks SystemConfigs
+
+impl IntoSystemSet<SC> for SystemConfigs {
+    type SysSet = SystemConfigs;
+
+    fn into_system_set(self) -> Self::SysSet {
+        self
+    }
+}
+
 macro_rules! impl_into_sys_set {
-    ($(($into_sys:ident, $mark:ident, $sys:ident)),*) => {
+    ($(($into_sys:ident, $mark:ident)),*) => {
         #[allow(non_snake_case, unused_parens)]
-        impl<$($into_sys,$mark,$sys: System + 'static),*> IntoSystemSet<($($mark),*)> for ($($into_sys,)*)
+        impl<$($into_sys,$mark),*> IntoSystemSet<($($mark),*)> for ($($into_sys,)*)
         where
-            $($into_sys: IntoSystem<$mark, System = $sys>),*
+            $($into_sys: IntoSystemSet<$mark>),*
         {
-            type SysSet = ($(Box<$into_sys::System>),*);
+            type SysSet = SystemConfigs;
 
             fn into_system_set(self) -> Self::SysSet {
                 let ($($into_sys,)*) = self;
-                ($(Box::new(IntoSystem::into_system($into_sys))),*)
+                let mut configs = SystemConfigs(Vec::new());
+                $(configs.append($into_sys.into_configs());)*
+                configs
             }
         }
     };
 }
 
-all_tuples!(impl_into_sys_set, 2, 15, S, M, T);
+all_tuples!(impl_into_sys_set, 2, 15, S, M);
 
 impl<I, M, S: System + 'static> IntoSystemSet<(M,)> for I
 where
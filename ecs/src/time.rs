@@ -0,0 +1,294 @@
+use std::time::{Duration, Instant};
+
+use crate::prelude::*;
+
+/// A clock, advanced once per [`World::update`] (see [`World::init_time`]).
+///
+/// The context `T` tells which clock it is: [`Real`], [`Virtual`] or [`Fixed`]. The generic
+/// `Time` (`Time<()>`) is the clock systems should usually read: it is the [`Virtual`] clock,
+/// except while [`FixedUpdate`] runs where it is the [`Fixed`] clock.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct Time<T: Default + Send + Sync + 'static = ()> {
+    context: T,
+    delta: Duration,
+    elapsed: Duration,
+    frame_count: u64,
+}
+
+impl<T: Default + Send + Sync + 'static> Time<T> {
+    pub fn new_with(context: T) -> Self {
+        Self {
+            context,
+            ..Default::default()
+        }
+    }
+
+    /// Advance the clock, and count a new frame.
+    pub fn advance_by(&mut self, delta: Duration) {
+        self.delta = delta;
+        self.elapsed += delta;
+        self.frame_count += 1;
+    }
+
+    /// The time between the last two advances.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// The time since the clock started.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn elapsed_seconds(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    /// How many times the clock advanced.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    pub fn context(&self) -> &T {
+        &self.context
+    }
+
+    pub fn context_mut(&mut self) -> &mut T {
+        &mut self.context
+    }
+
+    /// The clock, without its context.
+    pub fn as_generic(&self) -> Time {
+        Time {
+            context: (),
+            delta: self.delta,
+            elapsed: self.elapsed,
+            frame_count: self.frame_count,
+        }
+    }
+}
+
+/// The context of the wall clock, see [`TimeUpdateStrategy`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Real {
+    last_update: Option<Instant>,
+}
+
+/// The context of the game clock: the [`Real`] clock, capped, scaled and pausable.
+#[derive(Clone, Copy, Debug)]
+pub struct Virtual {
+    paused: bool,
+    relative_speed: f64,
+    /// The longest a single update can advance the clock, so a long freeze doesn't make the
+    /// [`FixedUpdate`] schedule run many times to catch up.
+    max_delta: Duration,
+}
+
+impl Default for Virtual {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            relative_speed: 1.0,
+            max_delta: Duration::from_millis(250),
+        }
+    }
+}
+
+impl Time<Virtual> {
+    pub fn pause(&mut self) {
+        self.context.paused = true;
+    }
+
+    pub fn unpause(&mut self) {
+        self.context.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.context.paused
+    }
+
+    pub fn relative_speed(&self) -> f64 {
+        self.context.relative_speed
+    }
+
+    /// Make the clock go `relative_speed` times as fast as the real clock.
+    pub fn set_relative_speed(&mut self, relative_speed: f64) {
+        assert!(
+            relative_speed >= 0.0 && relative_speed.is_finite(),
+            "The relative speed of the clock must be positive and finite, got {}",
+            relative_speed
+        );
+        self.context.relative_speed = relative_speed;
+    }
+
+    pub fn max_delta(&self) -> Duration {
+        self.context.max_delta
+    }
+
+    pub fn set_max_delta(&mut self, max_delta: Duration) {
+        self.context.max_delta = max_delta;
+    }
+
+    /// Advance by the real delta, capped and scaled. A paused clock still counts the frame.
+    fn advance_with_real_delta(&mut self, real_delta: Duration) {
+        let delta = if self.context.paused {
+            Duration::ZERO
+        } else {
+            real_delta
+                .min(self.context.max_delta)
+                .mul_f64(self.context.relative_speed)
+        };
+        self.advance_by(delta);
+    }
+}
+
+/// The context of the clock of the [`FixedUpdate`] schedule.
+#[derive(Clone, Copy, Debug)]
+pub struct Fixed {
+    timestep: Duration,
+    /// The virtual time that has yet to be consumed by fixed steps.
+    overstep: Duration,
+}
+
+impl Default for Fixed {
+    fn default() -> Self {
+        Self {
+            timestep: Duration::from_micros(15625),
+            overstep: Duration::ZERO,
+        }
+    }
+}
+
+impl Time<Fixed> {
+    /// A fixed clock advancing by `timestep` on each step.
+    pub fn from_duration(timestep: Duration) -> Self {
+        let mut time = Self::default();
+        time.set_timestep(timestep);
+        time
+    }
+
+    /// A fixed clock stepping `hz` times per (virtual) second.
+    pub fn from_hz(hz: f64) -> Self {
+        Self::from_duration(Duration::from_secs_f64(1.0 / hz))
+    }
+
+    pub fn timestep(&self) -> Duration {
+        self.context.timestep
+    }
+
+    pub fn set_timestep(&mut self, timestep: Duration) {
+        assert!(!timestep.is_zero(), "The fixed timestep can't be zero");
+        self.context.timestep = timestep;
+    }
+
+    pub fn set_timestep_hz(&mut self, hz: f64) {
+        self.set_timestep(Duration::from_secs_f64(1.0 / hz));
+    }
+
+    /// The accumulated time that wasn't enough for another step.
+    pub fn overstep(&self) -> Duration {
+        self.context.overstep
+    }
+
+    /// How far the clock is into the next step, from 0 to 1. Useful to interpolate.
+    pub fn overstep_fraction(&self) -> f32 {
+        self.context.overstep.as_secs_f32() / self.context.timestep.as_secs_f32()
+    }
+
+    fn accumulate(&mut self, delta: Duration) {
+        self.context.overstep += delta;
+    }
+
+    /// Take a step out of the accumulated time, if there is enough.
+    fn expend(&mut self) -> bool {
+        let timestep = self.context.timestep;
+        if self.context.overstep < timestep {
+            return false;
+        }
+        self.context.overstep -= timestep;
+        self.advance_by(timestep);
+        true
+    }
+}
+
+/// How the [`Real`] clock advances.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub enum TimeUpdateStrategy {
+    /// Use the time elapsed since the last update.
+    #[default]
+    Automatic,
+    /// Advance by the duration on each update, for deterministic tests.
+    ManualDuration(Duration),
+    /// Use the time elapsed since the last update, pretending that now is the given instant.
+    ManualInstant(Instant),
+}
+
+/// Advance the [`Real`] and [`Virtual`] clocks, and make [`Time`] the virtual clock.
+pub fn update_time(
+    strategy: Res<TimeUpdateStrategy>,
+    mut real: ResMut<Time<Real>>,
+    mut virt: ResMut<Time<Virtual>>,
+    mut time: ResMut<Time>,
+) {
+    let last_update = real.context.last_update;
+    let (now, delta) = match *strategy {
+        TimeUpdateStrategy::Automatic => {
+            let now = Instant::now();
+            (now, last_update.map_or(Duration::ZERO, |last| now - last))
+        }
+        TimeUpdateStrategy::ManualInstant(now) => {
+            (now, last_update.map_or(Duration::ZERO, |last| now - last))
+        }
+        TimeUpdateStrategy::ManualDuration(delta) => {
+            (last_update.unwrap_or_else(Instant::now) + delta, delta)
+        }
+    };
+    real.context.last_update = Some(now);
+    real.advance_by(delta);
+    virt.advance_with_real_delta(delta);
+    *time = virt.as_generic();
+}
+
+/// Run [`FixedUpdate`] once per [`Fixed`] step of accumulated virtual time. While it runs,
+/// [`Time`] is the fixed clock.
+pub fn run_fixed_update_schedule(world: &mut World) {
+    let delta = world.get_resource::<Time<Virtual>>().unwrap().delta();
+    world
+        .get_pure_resource_mut::<Time<Fixed>>()
+        .unwrap()
+        .accumulate(delta);
+
+    loop {
+        let fixed = world.get_pure_resource_mut::<Time<Fixed>>().unwrap();
+        if !fixed.expend() {
+            break;
+        }
+        let fixed = fixed.as_generic();
+        *world.get_pure_resource_mut::<Time>().unwrap() = fixed;
+        // The schedule may have no systems yet.
+        let _ = world.try_run_schedule(FixedUpdate);
+    }
+
+    let virt = world.get_resource::<Time<Virtual>>().unwrap().as_generic();
+    *world.get_pure_resource_mut::<Time>().unwrap() = virt;
+}
+
+impl World {
+    /// Add the [`Time`] resources, and the systems advancing them and running [`FixedUpdate`].
+    pub fn init_time(&mut self) {
+        if self.contains_resource::<Time>() {
+            return;
+        }
+        self.init_resource::<Time>();
+        self.init_resource::<Time<Real>>();
+        self.init_resource::<Time<Virtual>>();
+        self.init_resource::<Time<Fixed>>();
+        self.init_resource::<TimeUpdateStrategy>();
+        self.add_systems(First, update_time);
+        self.add_systems(RunFixedUpdateLoop, run_fixed_update_schedule);
+    }
+}
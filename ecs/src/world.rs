@@ -2,16 +2,24 @@ pub(crate) mod access_table;
 pub(crate) mod unsafe_world_cell;
 
 use std::any::TypeId;
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 use bevy_ptr::OwningPtr;
 use bevy_utils::{HashMap, HashSet};
 
-use crate::component::{Bundle, Component, ComponentDesc, ComponentId, ComponentStorage};
-use crate::entity::{Entity, EntityWorldMut};
-use crate::prelude::schedule::{ScheduleLabel, SCHEDULE_MAX_PLACE};
+use crate::archetype::{ArchetypeId, Archetypes, EntityLocation, TableId, Tables};
+use crate::change_detection::{ComponentTicks, Mut, Tick};
+use crate::component::{
+    Bundle, Component, ComponentDesc, ComponentId, ComponentStorage, StorageType,
+};
+use crate::entity::{Entities, Entity, EntityWorldMut};
 use crate::prelude::*;
 use crate::query::Query;
 use crate::resource::ResTable;
+use crate::system::executor;
+use crate::system::system_registry::RegisteredSystems;
 
 pub use access_table::AccessTable;
 pub use unsafe_world_cell::UnsafeWorldCell;
@@ -24,12 +32,26 @@ pub enum Access {
 
 /// The World
 pub struct World {
+    id: WorldId,
     pub(crate) resources: ResTable,
-    pub(crate) components: HashMap<ComponentId, ComponentStorage>,
-    systems: HashMap<usize, Vec<BoxedSystem>>,
-    schedule_labels: Vec<usize>,
-    entites: HashSet<Entity>,
+    pub(crate) components: HashMap<ComponentId, ComponentDesc>,
+    pub(crate) archetypes: Archetypes,
+    pub(crate) tables: Tables,
+    pub(crate) sparse_sets: HashMap<ComponentId, ComponentStorage>,
+    schedules: Schedules,
+    pub(crate) entities: Entities,
+    /// Swap the buffers of each [`Events`] resource added with [`World::add_event`].
+    event_updaters: Vec<fn(&mut World)>,
+    /// Commands recorded by systems, waiting to be applied.
+    pub(crate) command_queue: Mutex<CommandQueue>,
+    change_tick: AtomicU32,
+    last_change_tick: Tick,
+    /// The change tick of the last [`World::check_change_ticks`] that clamped the ticks.
+    last_check_tick: Tick,
     ran_once: bool,
+    /// What is done with the errors of systems.
+    error_handler: ErrorHandler,
+    pub(crate) registered_systems: RegisteredSystems,
 }
 
 impl World {
@@ -38,56 +60,156 @@ impl World {
         Self::default()
     }
 
+    /// The id of the world, unique among the worlds created by the process.
+    pub fn id(&self) -> WorldId {
+        self.id
+    }
+
     /// Insert a resource based off a default value.
     pub fn init_resource<R>(&mut self)
     where
         R: Resource + Default,
     {
-        self.resources.insert_resource(R::default());
+        self.insert_resource(R::default());
     }
 
     /// Insert a resource based off a given value.
     pub fn insert_resource<R: Resource>(&mut self, res: R) {
-        self.resources.insert_resource(res);
+        let change_tick = self.change_tick();
+        self.resources.insert_resource(res, change_tick);
+    }
+
+    /// Check if the resource was inserted into the world.
+    pub fn contains_resource<R: Resource>(&self) -> bool {
+        self.get_resource::<R>().is_ok()
+    }
+
+    /// Check that the resource can be fetched, without locking it.
+    pub(crate) fn check_resource<R: Resource>(&self) -> Result<(), ResQueryError> {
+        self.resources.check_resource::<R>()
+    }
+
+    /// Add the [`Events`] resource for events of type `T`, its buffers are swapped
+    /// at the start of every [`World::update`].
+    pub fn add_event<T: Event>(&mut self) {
+        if self.contains_resource::<Events<T>>() {
+            return;
+        }
+        self.init_resource::<Events<T>>();
+        self.event_updaters.push(|world| {
+            if let Some(events) = world.get_pure_resource_mut::<Events<T>>() {
+                events.update();
+            }
+        });
+    }
+
+    /// Send an event outside of a system, [`World::add_event`] must be called first.
+    pub fn send_event<T: Event>(&mut self, event: T) {
+        let change_tick = self.change_tick();
+        self.get_pure_resource_mut::<Events<T>>()
+            .unwrap_or_else(|| panic!("Event {} wasn't added", type_name::<T>()))
+            .send(event, change_tick);
     }
 
     /// Get a [`Res`] smart pointer (shared access) to the resource.
     /// Return [`ResQueryError`] corresponding to the error, if encountered one.
     pub fn get_resource<R: Resource>(&self) -> ResQueryResult<R> {
-        self.resources.get_resource::<R>()
+        self.get_resource_with_ticks::<R>(self.last_change_tick, self.change_tick())
     }
 
     /// Get a [`ResMut`] smart pointer (exclusive access) to the resource.
     /// Return [`ResQueryError`] corresponding to the error, if encountered one.
     pub fn get_resource_mut<R: Resource>(&self) -> ResMutQueryResult<R> {
-        self.resources.get_resource_mut::<R>()
+        self.get_resource_mut_with_ticks::<R>(self.last_change_tick, self.change_tick())
     }
 
-    /// Get a direct exclusive reference to the resource.
+    /// Like [`World::get_resource`], but detects changes since `last_run` instead of since the last [`World::update`].
+    pub(crate) fn get_resource_with_ticks<R: Resource>(
+        &self,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> ResQueryResult<'_, R> {
+        self.resources.get_resource::<R>(last_run, this_run)
+    }
+
+    /// Like [`World::get_resource_mut`], but detects changes since `last_run` and marks them at `this_run`.
+    pub(crate) fn get_resource_mut_with_ticks<R: Resource>(
+        &self,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> ResMutQueryResult<'_, R> {
+        self.resources.get_resource_mut::<R>(last_run, this_run)
+    }
+
+    /// Get a direct exclusive reference to the resource. The resource is marked as changed.
     pub fn get_pure_resource_mut<R: Resource>(&mut self) -> Option<&mut R> {
-        self.resources.get_direct_resource_mut()
+        let change_tick = self.change_tick();
+        self.resources.get_direct_resource_mut(change_tick)
+    }
+
+    /// The current change tick of the world, changes made outside of systems are marked with it.
+    pub fn change_tick(&self) -> Tick {
+        Tick::new(self.change_tick.load(Ordering::Acquire))
+    }
+
+    /// Increment the change tick of the world, and return the tick before the increment.
+    /// Every system run gets its own tick this way.
+    pub fn increment_change_tick(&self) -> Tick {
+        Tick::new(self.change_tick.fetch_add(1, Ordering::AcqRel))
+    }
+
+    /// Move the change tick of the world `ticks` ticks forward, as if that many systems ran.
+    /// Meant for testing change detection over long runs.
+    pub fn advance_change_tick(&mut self, ticks: u32) {
+        let change_tick = self.change_tick.get_mut();
+        *change_tick = change_tick.wrapping_add(ticks);
+    }
+
+    /// Clamp every tick older than [`MAX_CHANGE_AGE`],
+    /// so changes don't look new again once the change tick wraps around. It only does the work
+    /// once every [`CHECK_TICK_THRESHOLD`] ticks, and is called by [`World::update`].
+    ///
+    /// The ticks of [`Events`] aren't checked, they're dropped after two updates.
+    pub fn check_change_ticks(&mut self) {
+        let this_run = self.change_tick();
+        if this_run.get().wrapping_sub(self.last_check_tick.get()) < CHECK_TICK_THRESHOLD {
+            return;
+        }
+        self.tables.check_change_ticks(this_run);
+        for storage in self.sparse_sets.values_mut() {
+            storage.check_change_ticks(this_run);
+        }
+        self.resources.check_change_ticks(this_run);
+        self.schedules.check_change_ticks(this_run);
+        self.registered_systems.check_change_ticks(this_run);
+        self.last_change_tick.check_tick(this_run);
+        self.last_check_tick = this_run;
+    }
+
+    /// The change tick at the start of the last [`World::update`]. Queries and resources accessed
+    /// outside of systems detect the changes made since then.
+    pub fn last_change_tick(&self) -> Tick {
+        self.last_change_tick
     }
 
     /// Register a [`Component`]. If the component alredy exists, the method will not do anything.
     pub fn register_component<C: Component>(&mut self) {
         if !self.components.contains_key(&std::any::TypeId::of::<C>()) {
-            self.components.insert(
-                std::any::TypeId::of::<C>(),
-                ComponentStorage::init(ComponentDesc::new::<C>()),
-            );
+            self.register_desc(std::any::TypeId::of::<C>(), ComponentDesc::new::<C>());
         }
     }
 
     pub fn register_untyped_component(&mut self, comp: &dyn Component) {
         if !self.components.contains_key(&comp.comp_id()) {
-            self.components.insert(
+            self.register_desc(
                 comp.comp_id(),
-                ComponentStorage::init(ComponentDesc::from_desc(
+                ComponentDesc::from_desc(
                     comp.comp_id(),
                     comp.layout(),
                     comp.name(),
                     comp.drop_fn(),
-                )),
+                    comp.storage_type(),
+                ),
             );
         }
     }
@@ -98,24 +220,103 @@ impl World {
         layout: std::alloc::Layout,
         name: &'static str,
         drop_fn: Option<unsafe fn(OwningPtr<'_>)>,
+        storage_type: StorageType,
     ) {
         if !self.components.contains_key(&comp_id) {
-            self.components.insert(
+            self.register_desc(
                 comp_id,
-                ComponentStorage::init(ComponentDesc::from_desc(comp_id, layout, name, drop_fn)),
+                ComponentDesc::from_desc(comp_id, layout, name, drop_fn, storage_type),
             );
         }
     }
 
-    fn new_entity(&mut self) -> Entity {
-        let mut entity = Entity::from_raw(rand::random::<u32>());
-        while self.entites.contains(&entity) {
-            entity = Entity::from_raw(rand::random::<u32>());
+    fn register_desc(&mut self, comp_id: ComponentId, desc: ComponentDesc) {
+        if desc.storage_type() == StorageType::SparseSet {
+            self.sparse_sets
+                .insert(comp_id, ComponentStorage::init(desc.clone()));
         }
-        self.entites.insert(entity);
+        self.components.insert(comp_id, desc);
+    }
+
+    fn new_entity(&mut self) -> Entity {
+        self.flush_entities();
+        let entity = self.entities.alloc();
+        self.place_in_empty_archetype(entity);
         entity
     }
 
+    /// Make the entities reserved by [`Commands`] alive, without any components.
+    pub(crate) fn flush_entities(&mut self) {
+        if !self.entities.needs_flush() {
+            return;
+        }
+        for entity in self.entities.flush() {
+            self.place_in_empty_archetype(entity);
+        }
+    }
+
+    fn place_in_empty_archetype(&mut self, entity: Entity) {
+        let table_row = self.tables.get_mut(TableId::EMPTY).push_entity(entity);
+        let archetype_row = self
+            .archetypes
+            .get_mut(ArchetypeId::EMPTY)
+            .push_entity(entity, table_row);
+        self.entities.set_location(
+            entity,
+            EntityLocation {
+                archetype_id: ArchetypeId::EMPTY,
+                archetype_row,
+                table_id: TableId::EMPTY,
+                table_row,
+            },
+        );
+    }
+
+    /// Swap remove the entity at `location` from its archetype, and update the location of the
+    /// entity that took its place.
+    pub(crate) fn swap_remove_from_archetype(&mut self, location: EntityLocation) {
+        let archetype = self.archetypes.get_mut(location.archetype_id);
+        if let Some(moved) = archetype.swap_remove_entity(location.archetype_row) {
+            let mut moved_location = self.entities.location(moved).unwrap();
+            moved_location.archetype_row = location.archetype_row;
+            self.entities.set_location(moved, moved_location);
+        }
+    }
+
+    /// Swap remove the entity at `location` from its table, and update the location of the
+    /// entity that took its place. The components must already be removed from the columns.
+    pub(crate) fn swap_remove_from_table(&mut self, location: EntityLocation) {
+        let table = self.tables.get_mut(location.table_id);
+        if let Some(moved) = table.swap_remove_entity(location.table_row) {
+            let mut moved_location = self.entities.location(moved).unwrap();
+            moved_location.table_row = location.table_row;
+            self.archetypes
+                .get_mut(moved_location.archetype_id)
+                .set_table_row(moved_location.archetype_row, location.table_row);
+            self.entities.set_location(moved, moved_location);
+        }
+    }
+
+    /// Get the location of the [`Entity`] in the archetypes, or `None` if it doesn't exist.
+    pub fn entity_location(&self, entity: Entity) -> Option<EntityLocation> {
+        self.entities.location(entity)
+    }
+
+    /// All the archetypes of the world.
+    pub fn archetypes(&self) -> &Archetypes {
+        &self.archetypes
+    }
+
+    /// All the tables of the world.
+    pub fn tables(&self) -> &Tables {
+        &self.tables
+    }
+
+    /// Check if the [`Entity`] is alive in this world. Stale handles of despawned entities are not.
+    pub fn contains_entity(&self, entity: Entity) -> bool {
+        self.entities.contains(entity)
+    }
+
     /// Spawn an [`Entity`] without any components.
     pub fn spawn_empty(&mut self) -> EntityWorldMut<'_> {
         let new_entity = self.new_entity();
@@ -130,66 +331,188 @@ impl World {
         entity_world_mut
     }
 
+    /// Despawn the [`Entity`] and drop all of its components.
+    /// Returns `false` if the entity doesn't exist.
+    ///
+    /// The entity is removed from the [`Children`](crate::prelude::Children) of its parent, and
+    /// its children lose their [`Parent`](crate::prelude::Parent) and become roots. See [`World::despawn_recursive`] to despawn them too.
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        self.flush_entities();
+        self.detach_from_hierarchy(entity);
+        let Some(location) = self.entities.location(entity) else {
+            return false;
+        };
+        for comp_id in self.archetypes.get(location.archetype_id).component_ids() {
+            if let Some(sparse_set) = self.sparse_sets.get_mut(comp_id) {
+                sparse_set.remove(entity);
+            }
+        }
+        let table = self.tables.get_mut(location.table_id);
+        for comp_id in table.component_ids().to_vec() {
+            // SAFETY: Every column of the table has a row for each of its entities.
+            unsafe {
+                table
+                    .column_mut(comp_id)
+                    .unwrap()
+                    .swap_remove_and_drop(location.table_row)
+            };
+        }
+        self.swap_remove_from_table(location);
+        self.swap_remove_from_archetype(location);
+        self.entities.free(entity);
+        true
+    }
+
     /// Get the [`EntityWorldMut`] of an entity.
     pub fn entity(&mut self, entity: Entity) -> EntityWorldMut<'_> {
         assert!(
-            self.entites.contains(&entity),
+            self.contains_entity(entity),
             "Can't get entity that doesn't exist {:?}",
             entity
         );
         EntityWorldMut::from_world(self, entity)
     }
 
+    /// Get the (type erased) component of an entity and its ticks, from whichever storage it lives in.
+    fn get_component_with_ticks(
+        &self,
+        entity: Entity,
+        comp_id: ComponentId,
+    ) -> Option<(Ptr<'_>, &UnsafeCell<ComponentTicks>)> {
+        let location = self.entities.location(entity)?;
+        match self.tables.get(location.table_id).column(comp_id) {
+            Some(column) => Some((
+                column.get(location.table_row)?,
+                &column.get_ticks_slice()[location.table_row],
+            )),
+            None => {
+                let sparse_set = self.sparse_sets.get(&comp_id)?;
+                Some((sparse_set.get(entity)?, sparse_set.get_ticks(entity)?))
+            }
+        }
+    }
+
     /// Get shared access to the component of an entity.
     pub fn get_component<C: Component>(&self, entity: Entity) -> Option<&C> {
-        // SAFETY: C is guaranteed to be the correct type for the ComponentStorage.
-        unsafe {
-            self.components
-                .get(&ComponentId::of::<C>())?
-                .get_typed(entity)
-        }
+        let (ptr, _) = self.get_component_with_ticks(entity, comp_id::<C>())?;
+        // SAFETY: C is guaranteed to be the correct type for the storage.
+        unsafe { Some(ptr.deref::<C>()) }
     }
 
-    /// Get exclusive access to the component of an entity.
-    pub fn get_component_mut<C: Component>(&mut self, entity: Entity) -> Option<&mut C> {
-        // SAFETY: C is guaranteed to be the correct type for the ComponentStorage.
+    /// Get exclusive access to the component of an entity, mutating it marks it as changed.
+    pub fn get_component_mut<C: Component>(&mut self, entity: Entity) -> Option<Mut<'_, C>> {
+        let last_run = self.last_change_tick;
+        let this_run = self.change_tick();
+        let (ptr, ticks) = self.get_component_with_ticks(entity, comp_id::<C>())?;
+        // SAFETY: C is guaranteed to be the correct type for the storage, and we have exclusive
+        // access to the world so nothing else is referencing the component or its ticks.
         unsafe {
-            self.components
-                .get_mut(&ComponentId::of::<C>())?
-                .get_mut_typed(entity)
+            Some(Mut {
+                value: &mut *(ptr.as_ptr() as *mut C),
+                ticks: &mut *ticks.get(),
+                last_run,
+                this_run,
+            })
         }
     }
 
+    /// Iterate over all the alive entities, ordered by their index.
     pub fn all_entities(&self) -> impl Iterator<Item = Entity> + '_ {
-        self.entites.iter().copied()
+        self.entities.iter()
+    }
+
+    /// The amount of alive entities in the world.
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
     }
 
+    /// Query the entities of the world. Changes are detected since the last [`World::update`].
     pub fn query<Q: WorldQuery>(&self) -> Query<Q> {
-        Query::from_world(self)
+        self.query_filtered()
+    }
+
+    /// Like [`World::query`], but only matches entities that pass the [`QueryFilter`].
+    pub fn query_filtered<Q: WorldQuery, F: QueryFilter>(&self) -> Query<'_, Q, F> {
+        Query::from_world(self, self.last_change_tick, self.change_tick())
+    }
+
+    /// A [`QueryState`], to query the world repeatedly without matching every archetype each time.
+    pub fn query_state<Q: WorldQuery>(&self) -> QueryState<Q> {
+        QueryState::new(self)
     }
 
-    /// Run the system.
+    /// Like [`World::query_state`], but only matches entities that pass the [`QueryFilter`].
+    pub fn query_state_filtered<Q: WorldQuery, F: QueryFilter>(&self) -> QueryState<Q, F> {
+        QueryState::new(self)
+    }
+
+    /// Apply all the [`Commands`] recorded so far, including the ones recorded while applying them.
+    pub fn apply_commands(&mut self) {
+        loop {
+            let mut queue = std::mem::take(
+                self.command_queue
+                    .get_mut()
+                    .unwrap_or_else(|e| e.into_inner()),
+            );
+            queue.apply(self);
+            if self
+                .command_queue
+                .get_mut()
+                .unwrap_or_else(|e| e.into_inner())
+                .is_empty()
+            {
+                break;
+            }
+        }
+    }
+
+    /// Set what is done with the errors of systems: returned by fallible systems, or raised when
+    /// a system's params can't be fetched. Errors panic by default ([`panic_on_error`]).
+    pub fn set_error_handler(&mut self, handler: ErrorHandler) {
+        self.error_handler = handler;
+    }
+
+    /// Hand the error to the [`ErrorHandler`].
+    pub fn handle_error(&mut self, error: SystemError) {
+        (self.error_handler)(self, error);
+    }
+
+    /// Run the system, and apply its commands. Its error goes to the [`ErrorHandler`].
+    /// The system is built (and its params' state created) on every call, see
+    /// [`World::register_system`] to keep it between calls.
     pub fn run_system<M>(&mut self, sys: impl IntoSystem<M>) {
         let mut sys = IntoSystem::into_system(sys);
         if !sys.check_conflict() {
-            sys.run(self);
+            let result = sys.run(self);
+            self.apply_commands();
+            if let Err(error) = result {
+                self.handle_error(error);
+            }
         } else {
             panic!("System {} has conflicting access", sys.name());
         }
     }
 
-    /// Run a boxed system.
+    /// Run a boxed system, and apply its commands. Its error goes to the [`ErrorHandler`].
     pub fn run_boxed_system(&mut self, mut sys: BoxedSystem) {
         if !sys.check_conflict() {
-            sys.run(self);
+            let result = sys.run(self);
+            self.apply_commands();
+            if let Err(error) = result {
+                self.handle_error(error);
+            }
         } else {
             panic!("System {} has conflicting access", sys.name());
         }
     }
 
-    /// Run a boxed system without checking for conflicts in the params.
+    /// Run a boxed system without checking for conflicts in the params, and apply its commands.
     pub unsafe fn run_boxed_system_unchecked(&mut self, mut sys: BoxedSystem) {
-        sys.run(self);
+        let result = sys.run(self);
+        self.apply_commands();
+        if let Err(error) = result {
+            self.handle_error(error);
+        }
     }
 
     /// Run all the systems in the [`SystemSet`]
@@ -208,102 +531,140 @@ impl World {
         }
     }
 
-    /// Run the system without checking for conflicts in the params.
+    /// Run the system without checking for conflicts in the params, and apply its commands.
     pub unsafe fn run_system_unchecked<M>(&mut self, sys: impl IntoSystem<M>) {
-        IntoSystem::into_system(sys).run(self)
+        let result = IntoSystem::into_system(sys).run(self);
+        self.apply_commands();
+        if let Err(error) = result {
+            self.handle_error(error);
+        }
     }
 }
 
 impl World {
-    pub fn config_schedule_label<L>(&mut self)
-    where
-        L: ScheduleLabel,
-    {
-        let label = L::PLACE;
-        self.schedule_labels.sort();
-        if self.schedule_labels.binary_search(&label).is_err() {
-            self.schedule_labels.push(label);
-            self.systems.insert(label, Vec::new());
-            self.schedule_labels.sort();
-        }
+    pub fn schedules(&self) -> &Schedules {
+        &self.schedules
     }
-    pub fn add_systems<M, L>(&mut self, sys_set: impl IntoSystemSet<M>)
-    where
-        L: ScheduleLabel,
-    {
-        let label = L::PLACE;
-        self.config_schedule_label::<L>();
-        self.systems
-            .get_mut(&label)
-            .unwrap()
-            .extend(IntoSystemSet::into_system_set(sys_set).systems());
+
+    /// The schedules, to configure the order of the main schedules.
+    pub fn schedules_mut(&mut self) -> &mut Schedules {
+        &mut self.schedules
     }
 
-    pub fn run_schedule<L>(&mut self)
-    where
-        L: ScheduleLabel,
-    {
-        let label = L::PLACE;
-        self.run_schedule_value(label);
+    /// Create the schedule of the label, if it doesn't exist yet, so it can be run without systems.
+    pub fn init_schedule(&mut self, label: impl ScheduleLabel) {
+        self.schedules.entry(&label);
     }
 
-    pub(crate) fn run_schedule_value(&mut self, label: usize) {
-        if !self.schedule_labels.contains(&label) {
-            panic!("Can't run uninitialized schedule label {}", label);
-        }
-        let uwc = UnsafeWorldCell::from_world(self);
-        for sys in uwc.systems_from_schedule_label(label) {
-            // SAFETY: Systems can't access the systems themselves (kinda).
-            unsafe {
-                sys.run_unsafe(uwc);
-            }
-        }
+    pub fn add_systems<M>(&mut self, label: impl ScheduleLabel, sys_set: impl IntoSystemSet<M>) {
+        self.schedules
+            .entry(&label)
+            .add_systems(sys_set.into_configs());
     }
 
-    pub fn run_startup_labels(&mut self) {
-        let startup_labels = self
-            .schedule_labels
-            .iter()
-            .copied()
-            .filter(|l| *l < SCHEDULE_MAX_PLACE)
-            .collect::<Vec<_>>();
+    /// Only run the schedule of the label when the condition is true. The conditions of a schedule
+    /// are evaluated once, before any of its systems runs.
+    pub fn add_schedule_condition<M>(
+        &mut self,
+        label: impl ScheduleLabel,
+        condition: impl IntoCondition<M>,
+    ) {
+        self.schedules.entry(&label).add_condition(condition);
+    }
 
-        for startup_label in startup_labels {
-            self.run_schedule_value(startup_label);
+    /// Run the schedule of the label. Panics if it was never configured.
+    pub fn run_schedule(&mut self, label: impl ScheduleLabel) {
+        if let Err(err) = self.try_run_schedule(label) {
+            let mut configured = self
+                .schedules
+                .labels()
+                .map(|label| format!("{:?}", label))
+                .collect::<Vec<_>>();
+            configured.sort();
+            panic!("{} (configured schedules: {})", err, configured.join(", "));
         }
     }
 
-    pub fn run_update_labels(&mut self) {
-        let update_labels = self
-            .schedule_labels
-            .iter()
-            .copied()
-            .filter(|l| *l >= SCHEDULE_MAX_PLACE)
-            .collect::<Vec<_>>();
+    /// Run the schedule of the label, if it was configured.
+    pub fn try_run_schedule(
+        &mut self,
+        label: impl ScheduleLabel,
+    ) -> Result<(), ScheduleNotConfigured> {
+        self.run_schedule_dyn(&label)
+    }
+
+    fn run_schedule_dyn(&mut self, label: &dyn ScheduleLabel) -> Result<(), ScheduleNotConfigured> {
+        // The systems are taken out of the world while they run, so applying commands
+        // (or systems with exclusive access to the world) can't touch them.
+        let Some(mut schedule) = self.schedules.remove(label) else {
+            return Err(ScheduleNotConfigured(label.dyn_clone()));
+        };
+        schedule.run(self);
+        // Systems added to the schedule while it ran are sorted with the others on the next run.
+        self.schedules.put_back(label.dyn_clone(), schedule);
+        Ok(())
+    }
+
+    /// Run the startup schedules, unless they already ran. [`World::update`] runs them first.
+    pub fn run_startup_schedules(&mut self) {
+        if self.ran_once {
+            return;
+        }
+        self.ran_once = true;
+        for label in self.schedules.startup_order().to_vec() {
+            // Schedules of the order without systems are skipped.
+            let _ = self.run_schedule_dyn(label.as_ref());
+        }
+    }
 
-        for update_label in update_labels {
-            self.run_schedule_value(update_label);
+    /// Run the main schedules, in order.
+    pub fn run_main_schedules(&mut self) {
+        for label in self.schedules.main_order().to_vec() {
+            let _ = self.run_schedule_dyn(label.as_ref());
         }
     }
 
     pub fn update(&mut self) {
-        if !self.ran_once {
-            self.run_startup_labels();
-            self.ran_once = true;
+        self.last_change_tick = self.increment_change_tick();
+        self.run_startup_schedules();
+        for update_events in self.event_updaters.clone() {
+            update_events(self);
         }
-        self.run_update_labels();
+        self.run_main_schedules();
+        self.check_change_ticks();
+    }
+}
+
+/// Identifies a [`World`], see [`World::id`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WorldId(usize);
+
+impl WorldId {
+    fn new() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        WorldId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
     }
 }
 
 impl Default for World {
     fn default() -> Self {
         Self {
+            id: WorldId::new(),
             resources: ResTable::default(),
             components: HashMap::new(),
-            systems: HashMap::new(),
-            schedule_labels: Vec::new(),
-            entites: HashSet::new(),
+            archetypes: Archetypes::default(),
+            tables: Tables::default(),
+            sparse_sets: HashMap::new(),
+            schedules: Schedules::default(),
+            entities: Entities::default(),
+            event_updaters: Vec::new(),
+            command_queue: Mutex::new(CommandQueue::default()),
+            change_tick: AtomicU32::new(1),
+            last_change_tick: Tick::new(0),
+            last_check_tick: Tick::new(0),
             ran_once: false,
+            error_handler: panic_on_error,
+            registered_systems: RegisteredSystems::default(),
         }
     }
 }
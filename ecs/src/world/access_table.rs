@@ -28,7 +28,6 @@ impl std::ops::DerefMut for AccessTable {
     }
 }
 
-
 impl AccessTable {
     pub fn is_conflicted(&self) -> bool {
         self.conflict
@@ -56,11 +55,34 @@ impl AccessTable {
         }
     }
 
+    /// Check if holding both accesses at the same time would conflict, i.e. if either table
+    /// writes to something the other one accesses.
+    pub fn conflicts_with(&self, other: &AccessTable) -> bool {
+        self.iter().any(|(k, v)| {
+            other
+                .get(k)
+                .is_some_and(|o| *v == Access::Write || *o == Access::Write)
+        })
+    }
+
     pub fn single(type_id: TypeId, access: Access) -> Self {
         let mut access_table = Self::new();
         access_table.insert(type_id, access);
         access_table
     }
 
-    pub fn extend() {}
+    /// Add the accesses of `other` without treating overlaps as conflicts, keeping the
+    /// strongest access for each type. Used for accesses that are never held at the same
+    /// time, like the data and the filter of a single [`Query`].
+    pub fn extend(&mut self, other: Self) {
+        if other.conflict {
+            self.conflict = true
+        }
+
+        for (k, v) in other.table.into_iter() {
+            if v == Access::Write || !self.contains_key(&k) {
+                self.insert(k, v);
+            }
+        }
+    }
 }
@@ -1,9 +1,6 @@
 use std::marker::PhantomData;
 
-use crate::{
-    component::ComponentStorage,
-    prelude::{schedule::ScheduleLabel, *},
-};
+use crate::{component::ComponentStorage, prelude::*};
 
 #[derive(Clone, Copy)]
 pub struct UnsafeWorldCell<'w> {
@@ -11,6 +8,10 @@ pub struct UnsafeWorldCell<'w> {
     _marker: PhantomData<(&'w World,)>,
 }
 
+// SAFETY: The cell is only shared between systems whose accesses don't conflict (see `system::executor`).
+unsafe impl Send for UnsafeWorldCell<'_> {}
+unsafe impl Sync for UnsafeWorldCell<'_> {}
+
 impl<'w> UnsafeWorldCell<'w> {
     pub(crate) fn from_world(world: &'w World) -> Self {
         Self {
@@ -31,6 +32,10 @@ impl<'w> UnsafeWorldCell<'w> {
         unsafe { &mut *self.world_ptr }
     }
 
+    pub fn contains_entity(self, entity: Entity) -> bool {
+        unsafe { self.world() }.contains_entity(entity)
+    }
+
     pub fn all_entities(self) -> Vec<Entity> {
         unsafe { self.world() }.all_entities().collect()
     }
@@ -39,12 +44,24 @@ impl<'w> UnsafeWorldCell<'w> {
         unsafe { self.world() }.get_component::<C>(entity)
     }
 
-    pub fn get_component_mut<C: Component>(self, entity: Entity) -> Option<&'w mut C> {
+    pub fn get_component_mut<C: Component>(self, entity: Entity) -> Option<Mut<'w, C>> {
         unsafe { self.world_mut() }.get_component_mut::<C>(entity)
     }
 
-    pub fn component_storage(self, comp_id: ComponentId) -> Option<&'w ComponentStorage> {
-        unsafe { self.world() }.components.get(&comp_id)
+    pub fn entity_location(self, entity: Entity) -> Option<EntityLocation> {
+        unsafe { self.world() }.entity_location(entity)
+    }
+
+    pub fn archetypes(self) -> &'w Archetypes {
+        unsafe { self.world() }.archetypes()
+    }
+
+    pub fn tables(self) -> &'w Tables {
+        unsafe { self.world() }.tables()
+    }
+
+    pub(crate) fn sparse_set(self, comp_id: ComponentId) -> Option<&'w ComponentStorage> {
+        unsafe { self.world() }.sparse_sets.get(&comp_id)
     }
 
     pub fn get_resource<R: Resource>(self) -> ResQueryResult<'w, R> {
@@ -55,15 +72,11 @@ impl<'w> UnsafeWorldCell<'w> {
         unsafe { self.world_mut() }.get_resource_mut::<R>()
     }
 
-    pub fn query<Q: WorldQuery>(self) -> Query<'w, Q> {
-        Query::from_world(unsafe { self.world() })
-    }
-
-    pub fn systems_from_schedule<L: ScheduleLabel>(self) -> &'w mut Vec<BoxedSystem> {
-        self.systems_from_schedule_label(L::PLACE)
+    pub fn query<Q: WorldQuery, F: QueryFilter>(self) -> Query<'w, Q, F> {
+        unsafe { self.world() }.query_filtered()
     }
 
-    pub fn systems_from_schedule_label(self, label: usize) -> &'w mut Vec<BoxedSystem> {
-        unsafe { self.world_mut() }.systems.get_mut(&label).unwrap()
+    pub fn systems_from_schedule(self, label: impl ScheduleLabel) -> Option<&'w mut Schedule> {
+        unsafe { self.world_mut() }.schedules.get_mut(&label)
     }
 }
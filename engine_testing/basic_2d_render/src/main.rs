@@ -23,16 +23,15 @@ fn setup_sprites(mut sprites: ResMut<Sprites>) {
     println!("Loaded sprite!");
 }
 
-fn spawn_stars(world: &mut World, sprites: Res<Sprites>, input: Res<Keyboard>) {
+fn spawn_stars(mut commands: Commands, sprites: Res<Sprites>, input: Res<Keyboard>) {
     if input.pressed(KeyCode::Space) {
         println!("Spawing Star!");
         let sprite_handle = Arc::clone(sprites.0.get("Star").unwrap());
         let x = rand::random::<f32>() * 500.0;
         let y = rand::random::<f32>() * 500.0;
-        world.spawn(
-            SpriteBundle::from_sprite(sprite_handle).with_transform(Transform {
-                position: Vec3::new(x, y, 0.0),
-            }),
+        commands.spawn(
+            SpriteBundle::from_sprite(sprite_handle)
+                .with_transform(Transform::from_position(Vec3::new(x, y, 0.0))),
         );
     }
 }
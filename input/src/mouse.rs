@@ -37,6 +37,12 @@ impl Mouse {
         self.input().mouse_held(mouse_button_as_usize(button))
     }
 
+    /// Returns the position of the cursor in physical pixels of the window, or `None` if it's
+    /// outside of the window.
+    pub fn position(&self) -> Option<(f32, f32)> {
+        self.input().mouse()
+    }
+
     /// Returns the change in mouse coordinates that occured.
     pub fn diff(&self) -> (f32, f32) {
         self.input().mouse_diff()
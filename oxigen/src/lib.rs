@@ -10,6 +10,7 @@ pub mod prelude {
 
 #[allow(non_snake_case)]
 pub fn DefaultPlugins(app: &mut App) {
+    app.add_plugin(app::TimePlugin);
     app.add_plugin(runner::RunnerPlugin);
     app.add_plugin(render_2d::prelude::Render2dPipelinePlugin);
 }
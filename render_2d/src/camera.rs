@@ -0,0 +1,124 @@
+use bevy_math::{Affine2, UVec2, Vec2};
+use ecs::prelude::*;
+
+/// A rectangle of the surface, in pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    /// The top-left corner.
+    pub position: UVec2,
+    pub size: UVec2,
+}
+
+impl Viewport {
+    pub fn new(position: UVec2, size: UVec2) -> Self {
+        Self { position, size }
+    }
+
+    /// Whether the point, in pixels of the surface, is inside of the viewport.
+    pub fn contains(&self, point: Vec2) -> bool {
+        let min = self.position.as_vec2();
+        let max = min + self.size.as_vec2();
+        point.cmpge(min).all() && point.cmplt(max).all()
+    }
+}
+
+/// What part of the world is drawn where on the surface. Without cameras, the world coordinates
+/// are the pixels of the surface. With cameras, every camera draws the sprites in its viewport.
+///
+/// The world is moved so the camera's position is at the center of the viewport, then rotated
+/// and zoomed around it.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Camera2d {
+    /// The point of the world at the center of the viewport.
+    pub position: Vec2,
+    /// How many pixels a unit of the world covers. Above 1 zooms in, below 1 zooms out. It can't
+    /// be 0.
+    pub zoom: f32,
+    /// The rotation of the camera in radians. Positive angles turn the camera clockwise, so the
+    /// world turns counterclockwise on screen.
+    pub rotation: f32,
+    /// Where the camera draws on the surface, or the whole surface if `None`. It's clipped to the
+    /// surface.
+    pub viewport: Option<Viewport>,
+    /// Cameras with the lowest order draw first, the ones with the highest order draw over them.
+    pub order: i32,
+}
+
+impl Default for Camera2d {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            zoom: 1.0,
+            rotation: 0.0,
+            viewport: None,
+            order: 0,
+        }
+    }
+}
+
+impl Camera2d {
+    pub fn from_position(position: Vec2) -> Self {
+        Self {
+            position,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_zoom(mut self, zoom: f32) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_viewport(mut self, viewport: Viewport) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    pub fn with_order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// The viewport on a surface of `surface_size`, clipped to it.
+    pub fn viewport_rect(&self, surface_size: UVec2) -> Viewport {
+        let viewport = self
+            .viewport
+            .unwrap_or(Viewport::new(UVec2::ZERO, surface_size));
+        let position = viewport.position.min(surface_size);
+        Viewport::new(position, viewport.size.min(surface_size - position))
+    }
+
+    /// The transform from the world to the pixels of the viewport, `(0, 0)` being its top-left
+    /// corner.
+    pub fn view_affine(&self, viewport_size: UVec2) -> Affine2 {
+        let center = viewport_size.as_vec2() / 2.0;
+        Affine2::from_translation(center)
+            * Affine2::from_scale_angle_translation(
+                Vec2::splat(self.zoom),
+                -self.rotation,
+                Vec2::ZERO,
+            )
+            * Affine2::from_translation(-self.position)
+    }
+
+    /// Where the point of the world is drawn, in pixels of a surface of `surface_size`.
+    pub fn world_to_surface(&self, surface_size: UVec2, point: Vec2) -> Vec2 {
+        let viewport = self.viewport_rect(surface_size);
+        viewport.position.as_vec2() + self.view_affine(viewport.size).transform_point2(point)
+    }
+
+    /// The point of the world drawn at `point`, in pixels of a surface of `surface_size`. See
+    /// [`SurfaceBuffer::window_to_surface`](crate::prelude::SurfaceBuffer::window_to_surface)
+    /// for the position of the cursor.
+    pub fn surface_to_world(&self, surface_size: UVec2, point: Vec2) -> Vec2 {
+        let viewport = self.viewport_rect(surface_size);
+        self.view_affine(viewport.size)
+            .inverse()
+            .transform_point2(point - viewport.position.as_vec2())
+    }
+}
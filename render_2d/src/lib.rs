@@ -1,11 +1,15 @@
+mod camera;
 mod drawable;
 mod rendering_pipeline;
 mod sprite;
 mod transform;
 
 pub mod prelude {
+    pub use crate::camera::{Camera2d, Viewport};
     pub use crate::drawable::Drawable;
     pub use crate::rendering_pipeline::*;
-    pub use crate::sprite::{Sprite, SpriteBundle};
-    pub use crate::transform::{Transform, Vec3};
+    pub use crate::sprite::{RgbaImage, Sprite, SpriteBundle};
+    pub use crate::transform::{
+        propagate_transforms, Affine2, GlobalTransform, Mat2, Mat3, Transform, UVec2, Vec2, Vec3,
+    };
 }
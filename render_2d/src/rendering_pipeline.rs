@@ -1,73 +1,100 @@
 mod draw;
 mod plugin;
+mod raster;
+mod target;
 
-use crate::{prelude::Drawable, transform::Transform};
-use bevy_math::Vec3Swizzles;
+use crate::prelude::Drawable;
+use bevy_math::{UVec2, Vec2};
 use ecs::prelude::*;
-use pixels::Pixels;
-pub use plugin::{Render2dPipelinePlugin, Render2dPlugin};
+use image::RgbaImage;
+pub use plugin::{HeadlessRender2dPlugin, Render2dPipelinePlugin, Render2dPlugin};
+pub use raster::{BlendMode, Frame, Sampling};
 use std::sync::Arc;
+pub use target::RenderTarget;
 
 pub(crate) trait DrawableEntity: Drawable + Component {}
 
 impl<T> DrawableEntity for T where T: Drawable + Component {}
 
+/// The [`RenderTarget`] the sprites are drawn to on every [`Render`]. Inserted by
+/// [`Render2dPlugin`] for a window, or by [`HeadlessRender2dPlugin`] for an [`RgbaImage`].
 #[derive(Resource)]
 pub struct SurfaceBuffer {
-    width: usize,
-    height: usize,
-    pixels: Pixels,
+    target: Box<dyn RenderTarget>,
 }
 
 impl SurfaceBuffer {
-    pub(crate) fn draw_entity(&mut self, to_draw: &ToDraw) {
-        if let Ok(tl /* top left */) =
-            self.window_pos_to_pixel(to_draw.transform.position.xy().into())
-        {
-            let e_w = to_draw.drawable.width() as usize; // Entity width
-            let e_h = to_draw.drawable.height() as usize; // Entity height
-            let br = ((tl.0 + e_w).min(self.width), (tl.1 + e_h).min(self.height));
-            let (l_x, t_y) = tl;
-            let (r_x, b_y) = br;
-
-            let src = to_draw.drawable.buffer().as_flat_samples().samples;
-            let buff = self.pixels.frame_mut();
-            println!("\n");
-            println!("\n");
-            println!("\n");
-            for y in t_y..b_y {
-                let row_start = y * self.width + l_x;
-                let row_end = row_start + (r_x - l_x);
-                let e_row_start = (y - t_y) * e_w;
-                let e_row_end = e_row_start + (r_x - l_x);
-                // Take into account everything is 4x (4 bytes per pixel).
-                let row_start = row_start * 4;
-                let row_end = row_end * 4;
-                let e_row_start = e_row_start * 4;
-                let e_row_end = e_row_end * 4;
-                buff[row_start..row_end].copy_from_slice(&src[e_row_start..e_row_end]);
-            }
+    pub fn new(target: impl RenderTarget) -> Self {
+        Self {
+            target: Box::new(target),
         }
     }
-}
 
-#[derive(Component)]
-pub(crate) struct ToDraw {
-    pub(crate) transform: Transform,
-    pub(crate) drawable: Arc<dyn DrawableEntity>,
-}
+    pub fn width(&self) -> usize {
+        self.target.size().0
+    }
+
+    pub fn height(&self) -> usize {
+        self.target.size().1
+    }
+
+    pub fn size(&self) -> UVec2 {
+        let (width, height) = self.target.size();
+        UVec2::new(width as u32, height as u32)
+    }
 
-impl std::ops::Deref for SurfaceBuffer {
-    type Target = Pixels;
+    /// Fill the whole frame with the RGBA color.
+    pub fn clear(&mut self, color: [u8; 4]) {
+        self.frame().clear(color);
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.pixels
+    /// The pixels of the surface, to draw to.
+    pub fn frame(&mut self) -> Frame<'_> {
+        let (width, height) = self.target.size();
+        Frame::new(self.target.pixels_mut(), width, height)
+    }
+
+    /// A copy of the frame, as drawn by the last [`Render`].
+    pub fn to_image(&self) -> RgbaImage {
+        let (width, height) = self.target.size();
+        RgbaImage::from_raw(width as u32, height as u32, self.target.pixels().to_vec())
+            .expect("the render target has as many pixels as its size")
+    }
+
+    /// Show the frame drawn, see [`RenderTarget::present`].
+    pub fn present(&mut self) -> Result<(), BoxedError> {
+        self.target.present()
+    }
+
+    /// The pixel of the surface at the `position` in physical pixels of the window, e.g. the
+    /// position of the cursor. Positions outside of the surface aren't clamped to it.
+    pub fn window_to_surface(&self, position: Vec2) -> Vec2 {
+        self.target.window_to_surface(position)
     }
 }
 
-/// Schedule label for executing the render-related systems
-pub struct Render;
+/// What the frame is cleared to at the start of every [`Render`], before the sprites are drawn.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClearColor {
+    /// Clear the frame to the RGBA color.
+    Color([u8; 4]),
+    /// Keep the previous frame beneath the sprites, for accumulation effects like trails.
+    Disabled,
+}
 
-impl ScheduleLabel for Render {
-    const PLACE: usize = 99;
+impl Default for ClearColor {
+    fn default() -> Self {
+        ClearColor::Color([0, 0, 0, 255])
+    }
 }
+
+/// What's drawn for an entity, at its [`GlobalTransform`].
+#[derive(Component)]
+pub(crate) struct ToDraw {
+    pub(crate) drawable: Arc<dyn DrawableEntity>,
+}
+
+/// Schedule label for executing the render-related systems. It isn't a main schedule, the runner
+/// runs it whenever the window has to be redrawn.
+#[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Render;
@@ -1,22 +1,72 @@
 use std::cmp::Ordering;
 
-use super::{SurfaceBuffer, ToDraw};
+use super::{BlendMode, ClearColor, Frame, Sampling, SurfaceBuffer, ToDraw};
+use crate::{camera::Camera2d, transform::GlobalTransform};
+use bevy_math::{Affine2, UVec2};
 use ecs::prelude::*;
 
+type ToDrawItem<'a> = (
+    &'a ToDraw,
+    &'a GlobalTransform,
+    Option<&'a Sampling>,
+    Option<&'a BlendMode>,
+);
+
+/// Clear the frame to the [`ClearColor`], unless clearing is disabled.
+pub fn clear_frame(clear_color: Res<ClearColor>, mut surface_buffer: ResMut<SurfaceBuffer>) {
+    if let ClearColor::Color(color) = *clear_color {
+        surface_buffer.clear(color);
+    }
+}
+
+/// Draw the sprites in the viewport of every [`Camera2d`], in order. Without cameras, the sprites
+/// are drawn at their position in pixels of the surface.
 pub fn draw_entites_to_draw(
-    drawable_entities: Query<&ToDraw>,
+    cameras: Query<&Camera2d>,
+    drawable_entities: Query<(
+        &ToDraw,
+        &GlobalTransform,
+        Option<&Sampling>,
+        Option<&BlendMode>,
+    )>,
     mut surface_buffer: ResMut<SurfaceBuffer>,
 ) {
-    let mut drawables: Vec<&ToDraw> = drawable_entities.into_iter().collect();
-    drawables.sort_by(|a, b| {
-        a.transform
-            .position
+    let mut drawables: Vec<_> = drawable_entities.into_iter().collect();
+    drawables.sort_by(|(_, a, ..), (_, b, ..)| {
+        a.position()
             .z
-            .partial_cmp(&b.transform.position.z)
+            .partial_cmp(&b.position().z)
             .unwrap_or(Ordering::Equal)
     });
-    for drawable in drawables {
-        // println!("Drawing entity");
-        surface_buffer.draw_entity(drawable);
+    let mut cameras: Vec<_> = cameras.into_iter().collect();
+    cameras.sort_by_key(|camera| camera.order);
+
+    let surface_size = surface_buffer.size();
+    let mut frame = surface_buffer.frame();
+    if cameras.is_empty() {
+        draw_all(&mut frame, Affine2::IDENTITY, &drawables);
+    }
+    for camera in cameras {
+        let viewport = camera.viewport_rect(surface_size);
+        let UVec2 { x, y } = viewport.position;
+        let mut view = frame.viewport(
+            x as usize,
+            y as usize,
+            viewport.size.x as usize,
+            viewport.size.y as usize,
+        );
+        draw_all(&mut view, camera.view_affine(viewport.size), &drawables);
+    }
+}
+
+/// Draw the sprites, `view` mapping the world to the pixels of the frame.
+fn draw_all(frame: &mut Frame, view: Affine2, drawables: &[ToDrawItem]) {
+    for (drawable, transform, sampling, blend_mode) in drawables {
+        frame.draw(
+            &*drawable.drawable,
+            view * transform.affine(),
+            sampling.copied().unwrap_or_default(),
+            blend_mode.copied().unwrap_or_default(),
+        );
     }
 }
@@ -1,5 +1,12 @@
-use super::{draw::draw_entites_to_draw, Render, SurfaceBuffer};
+use super::{
+    draw::{clear_frame, draw_entites_to_draw},
+    target::WindowSurface,
+    ClearColor, Render, SurfaceBuffer,
+};
+use crate::transform::propagate_transforms;
 use app::*;
+use ecs::prelude::{IntoSystemSet, PostUpdate};
+use image::RgbaImage;
 use pixels::{Pixels, SurfaceTexture};
 use winit::window::Window;
 
@@ -24,15 +31,48 @@ impl Render2dPlugin {
 
 impl WorldPlugin for Render2dPlugin {
     fn build(self, world: &mut ecs::prelude::World) {
-        world.insert_resource(SurfaceBuffer {
+        world.insert_resource(SurfaceBuffer::new(WindowSurface {
             width: self.width,
             height: self.height,
             pixels: self.pixels,
-        });
+        }));
     }
 }
 
+/// Renders to an [`RgbaImage`] instead of a window, for tests or servers without a display.
+/// Read the frame back with [`SurfaceBuffer::to_image`] after running the [`Render`] schedule.
+pub struct HeadlessRender2dPlugin {
+    width: usize,
+    height: usize,
+}
+
+impl HeadlessRender2dPlugin {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, height }
+    }
+}
+
+impl WorldPlugin for HeadlessRender2dPlugin {
+    fn build(self, world: &mut ecs::prelude::World) {
+        let image = RgbaImage::new(self.width as u32, self.height as u32);
+        world.insert_resource(SurfaceBuffer::new(image));
+    }
+}
+
+/// Without a window, the plugin doesn't have to wait for the runner.
+impl Plugin for HeadlessRender2dPlugin {
+    fn build(self, app: &mut App) {
+        WorldPlugin::build(self, app.world());
+    }
+}
+
+/// Propagates the transforms, then clears the frame to the [`ClearColor`] and draws the sprites
+/// on every [`Render`]. A [`ClearColor`] inserted before the plugin is kept.
 #[allow(non_snake_case)]
 pub fn Render2dPipelinePlugin(app: &mut App) {
-    app.add_systems(Render, draw_entites_to_draw);
+    if !app.world().contains_resource::<ClearColor>() {
+        app.init_resource::<ClearColor>();
+    }
+    app.add_systems(PostUpdate, propagate_transforms);
+    app.add_systems(Render, (clear_frame, draw_entites_to_draw).chain());
 }
@@ -0,0 +1,260 @@
+use crate::drawable::Drawable;
+use bevy_math::{Affine2, Mat2, Vec2};
+use ecs::prelude::*;
+use image::RgbaImage;
+
+/// How the pixels of a rotated or scaled sprite are sampled. Sprites without it use
+/// [`Sampling::Nearest`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Sampling {
+    /// The closest pixel of the sprite, keeps pixel art crisp.
+    #[default]
+    Nearest,
+    /// The four closest pixels weighted by their distance, smooths the edges.
+    Bilinear,
+}
+
+/// How the pixels of a sprite are combined with the pixels already drawn beneath them. Sprites
+/// without it use [`BlendMode::Alpha`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Source-over blending: transparent pixels of the sprite let what's beneath show through.
+    #[default]
+    Alpha,
+    /// Add the color of the sprite, weighted by its alpha, to what's beneath. Brightens, for
+    /// lights and glows.
+    Additive,
+    /// Multiply what's beneath by the color of the sprite, weighted by its alpha. Darkens, for
+    /// shadows and tints.
+    Multiply,
+    /// Copy the pixels of the sprite, alpha included. The fastest, for sprites without
+    /// transparency.
+    Opaque,
+}
+
+impl BlendMode {
+    /// Combine the `src` pixel of a sprite with the `dst` pixel beneath it, both RGBA.
+    /// The alpha of the result is the source-over alpha, except for [`BlendMode::Opaque`].
+    pub fn blend(self, dst: [u8; 4], src: [u8; 4]) -> [u8; 4] {
+        let alpha = src[3] as u32;
+        match self {
+            BlendMode::Opaque => return src,
+            _ if alpha == 0 => return dst,
+            BlendMode::Alpha if alpha == 255 => return src,
+            _ => {}
+        }
+        let mut out = [0; 4];
+        for channel in 0..3 {
+            let (d, s) = (dst[channel] as u32, src[channel] as u32);
+            out[channel] = match self {
+                BlendMode::Alpha => div_255(s * alpha + d * (255 - alpha)),
+                BlendMode::Additive => (d + div_255(s * alpha)).min(255),
+                BlendMode::Multiply => div_255(d * (div_255(s * alpha) + 255 - alpha)),
+                BlendMode::Opaque => unreachable!(),
+            } as u8;
+        }
+        out[3] = (alpha + div_255(dst[3] as u32 * (255 - alpha))) as u8;
+        out
+    }
+}
+
+/// Divide by 255, rounding to the nearest integer.
+fn div_255(value: u32) -> u32 {
+    (value + 127) / 255
+}
+
+/// RGBA pixels to draw sprites to, row by row, 4 bytes per pixel. Sprites are clipped to it, they
+/// can be partially or entirely outside of it, on any side.
+pub struct Frame<'a> {
+    pixels: &'a mut [u8],
+    width: usize,
+    height: usize,
+    /// The number of pixels from the start of a row to the start of the next one, more than the
+    /// width for a viewport of a larger frame.
+    stride: usize,
+}
+
+impl<'a> Frame<'a> {
+    /// Panics if there aren't exactly `width * height` RGBA pixels.
+    pub fn new(pixels: &'a mut [u8], width: usize, height: usize) -> Self {
+        assert_eq!(
+            pixels.len(),
+            width * height * 4,
+            "a {width}x{height} frame needs {} bytes",
+            width * height * 4
+        );
+        Self {
+            pixels,
+            width,
+            height,
+            stride: width,
+        }
+    }
+
+    /// The rectangle of the frame at `(x, y)` of size `(width, height)`, clipped to the frame.
+    /// Drawing to it draws to this frame, with `(0, 0)` being the top-left corner of the rectangle.
+    pub fn viewport(&mut self, x: usize, y: usize, width: usize, height: usize) -> Frame<'_> {
+        let x = x.min(self.width);
+        let y = y.min(self.height);
+        let width = width.min(self.width - x);
+        let height = height.min(self.height - y);
+        let start = ((y * self.stride + x) * 4).min(self.pixels.len());
+        Frame {
+            pixels: &mut self.pixels[start..],
+            width,
+            height,
+            stride: self.stride,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Fill the whole frame with the RGBA color.
+    pub fn clear(&mut self, color: [u8; 4]) {
+        for y in 0..self.height {
+            let start = y * self.stride * 4;
+            for pixel in self.pixels[start..start + self.width * 4].chunks_exact_mut(4) {
+                pixel.copy_from_slice(&color);
+            }
+        }
+    }
+
+    /// The RGBA pixel at `(x, y)`, or `None` outside of the frame.
+    pub fn pixel(&self, x: usize, y: usize) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let start = (y * self.stride + x) * 4;
+        Some(self.pixels[start..start + 4].try_into().unwrap())
+    }
+
+    /// Draw the sprite, `affine` mapping its pixels to the pixels of the frame: its top-left
+    /// corner is drawn at the translation, scaled and rotated around it by the matrix.
+    pub fn draw<D: Drawable + ?Sized>(
+        &mut self,
+        drawable: &D,
+        affine: Affine2,
+        sampling: Sampling,
+        blend_mode: BlendMode,
+    ) {
+        let origin = affine.translation;
+        // Only opaque sprites that aren't rotated, scaled or between pixels can be copied row by
+        // row.
+        if affine.matrix2 == Mat2::IDENTITY
+            && blend_mode == BlendMode::Opaque
+            && origin == origin.floor()
+        {
+            self.copy(drawable.buffer(), origin.x as i64, origin.y as i64);
+        } else {
+            self.draw_transformed(
+                drawable.buffer(),
+                origin,
+                affine.matrix2,
+                sampling,
+                blend_mode,
+            );
+        }
+    }
+
+    /// Copy the rows of the sprite placed at `(x, y)` that overlap the frame.
+    fn copy(&mut self, sprite: &RgbaImage, x: i64, y: i64) {
+        let sprite_width = sprite.width() as i64;
+        let left = x.max(0);
+        let top = y.max(0);
+        let right = (x + sprite_width).min(self.width as i64);
+        let bottom = (y + sprite.height() as i64).min(self.height as i64);
+        if left >= right || top >= bottom {
+            return;
+        }
+
+        let src = sprite.as_raw();
+        // Take into account everything is 4x (4 bytes per pixel).
+        let row_len = (right - left) as usize * 4;
+        for row in top..bottom {
+            let src_start = (((row - y) * sprite_width + (left - x)) * 4) as usize;
+            let dst_start = ((row * self.stride as i64 + left) * 4) as usize;
+            self.pixels[dst_start..dst_start + row_len]
+                .copy_from_slice(&src[src_start..src_start + row_len]);
+        }
+    }
+
+    /// Draw the sprite scaled and rotated by `matrix` around its top-left corner, placed at
+    /// `origin`. Only the pixels of the frame whose center the sprite covers are blended with the
+    /// sprite.
+    fn draw_transformed(
+        &mut self,
+        sprite: &RgbaImage,
+        origin: Vec2,
+        matrix: Mat2,
+        sampling: Sampling,
+        blend_mode: BlendMode,
+    ) {
+        // A zero scale draws nothing, and can't be inverted.
+        if matrix.determinant() == 0.0 {
+            return;
+        }
+        let inverse = matrix.inverse();
+        let size = Vec2::new(sprite.width() as f32, sprite.height() as f32);
+        let corners = [
+            Vec2::ZERO,
+            Vec2::new(size.x, 0.0),
+            Vec2::new(0.0, size.y),
+            size,
+        ]
+        .map(|corner| origin + matrix * corner);
+        let min = corners.iter().fold(Vec2::INFINITY, |min, &c| min.min(c));
+        let max = corners
+            .iter()
+            .fold(Vec2::NEG_INFINITY, |max, &c| max.max(c));
+        // Float to integer casts saturate, the bounds are clipped to the frame on all sides.
+        let left = min.x.floor().max(0.0) as usize;
+        let top = min.y.floor().max(0.0) as usize;
+        let right = (max.x.ceil().max(0.0) as usize).min(self.width);
+        let bottom = (max.y.ceil().max(0.0) as usize).min(self.height);
+
+        for y in top..bottom {
+            for x in left..right {
+                let center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let local = inverse * (center - origin);
+                if local.x < 0.0 || local.y < 0.0 || local.x >= size.x || local.y >= size.y {
+                    continue;
+                }
+                let color = match sampling {
+                    Sampling::Nearest => sprite.get_pixel(local.x as u32, local.y as u32).0,
+                    Sampling::Bilinear => sample_bilinear(sprite, local),
+                };
+                let start = (y * self.stride + x) * 4;
+                let pixel = &mut self.pixels[start..start + 4];
+                let blended = blend_mode.blend(pixel.try_into().unwrap(), color);
+                pixel.copy_from_slice(&blended);
+            }
+        }
+    }
+}
+
+/// Interpolate the four pixels around `local`, pixel centers being at half coordinates.
+/// Outside of the sprite, the edge pixels are repeated.
+fn sample_bilinear(sprite: &RgbaImage, local: Vec2) -> [u8; 4] {
+    let position = local - 0.5;
+    let floor = position.floor();
+    let fraction = position - floor;
+    let max_x = sprite.width() as i64 - 1;
+    let max_y = sprite.height() as i64 - 1;
+    let texel = |dx: i64, dy: i64| {
+        let x = (floor.x as i64 + dx).clamp(0, max_x) as u32;
+        let y = (floor.y as i64 + dy).clamp(0, max_y) as u32;
+        sprite.get_pixel(x, y).0.map(f32::from)
+    };
+    let lerp = |a: [f32; 4], b: [f32; 4], t: f32| {
+        [0, 1, 2, 3].map(|channel| a[channel] + (b[channel] - a[channel]) * t)
+    };
+    let top = lerp(texel(0, 0), texel(1, 0), fraction.x);
+    let bottom = lerp(texel(0, 1), texel(1, 1), fraction.x);
+    lerp(top, bottom, fraction.y).map(|channel| channel.round() as u8)
+}
@@ -0,0 +1,72 @@
+use bevy_math::Vec2;
+use ecs::prelude::BoxedError;
+use image::RgbaImage;
+use pixels::Pixels;
+
+/// What the sprites are drawn to: RGBA pixels, row by row, 4 bytes per pixel.
+pub trait RenderTarget: Send + Sync + 'static {
+    /// The width and height in pixels.
+    fn size(&self) -> (usize, usize);
+
+    fn pixels(&self) -> &[u8];
+
+    fn pixels_mut(&mut self) -> &mut [u8];
+
+    /// Show the frame drawn, on a window for example. Does nothing by default.
+    fn present(&mut self) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    /// The pixel at the `position` in physical pixels of the window, if there's one. The same
+    /// position by default.
+    fn window_to_surface(&self, position: Vec2) -> Vec2 {
+        position
+    }
+}
+
+/// Renders on the CPU only, without a window.
+impl RenderTarget for RgbaImage {
+    fn size(&self) -> (usize, usize) {
+        (self.width() as usize, self.height() as usize)
+    }
+
+    fn pixels(&self) -> &[u8] {
+        self.as_raw()
+    }
+
+    fn pixels_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+/// The surface of a window, scaled to fill it.
+pub(crate) struct WindowSurface {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) pixels: Pixels,
+}
+
+impl RenderTarget for WindowSurface {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn pixels(&self) -> &[u8] {
+        self.pixels.frame()
+    }
+
+    fn pixels_mut(&mut self) -> &mut [u8] {
+        self.pixels.frame_mut()
+    }
+
+    fn present(&mut self) -> Result<(), BoxedError> {
+        self.pixels.render().map_err(|err| err.to_string().into())
+    }
+
+    fn window_to_surface(&self, position: Vec2) -> Vec2 {
+        match self.pixels.window_pos_to_pixel(position.into()) {
+            Ok((x, y)) => Vec2::new(x as f32, y as f32),
+            Err((x, y)) => Vec2::new(x as f32, y as f32),
+        }
+    }
+}
@@ -1,11 +1,11 @@
 use core::fmt;
 use std::{fmt::Formatter, path::Path, sync::Arc};
 
-use crate::rendering_pipeline::ToDraw;
+use crate::rendering_pipeline::{DrawableEntity, ToDraw};
 
-use super::transform::Transform;
+use super::transform::{GlobalTransform, Transform};
 use ecs::prelude::*;
-use image::RgbaImage;
+pub use image::RgbaImage;
 
 #[derive(Component)]
 pub struct Sprite {
@@ -22,11 +22,15 @@ impl Sprite {
             .ok()?
             .into_rgba8();
 
-        Some(Sprite {
+        Some(Self::from_image(img))
+    }
+
+    pub fn from_image(img: RgbaImage) -> Self {
+        Sprite {
             width: img.width(),
             height: img.height(),
             pixels: img,
-        })
+        }
     }
 }
 
@@ -44,14 +48,14 @@ impl fmt::Debug for Sprite {
 }
 
 pub struct SpriteBundle {
-    sprite: Arc<Sprite>,
+    drawable: Arc<dyn DrawableEntity>,
     transform: Transform,
 }
 
 impl SpriteBundle {
     pub fn from_sprite(sprite: Arc<Sprite>) -> Self {
         Self {
-            sprite,
+            drawable: sprite,
             transform: Transform::default(),
         }
     }
@@ -62,11 +66,29 @@ impl SpriteBundle {
     }
 }
 
+/// The components of a sprite: its [`Transform`], a [`GlobalTransform`] computed from it, and
+/// what's drawn.
 impl Bundle for SpriteBundle {
     fn components(self) -> Vec<Box<dyn Component>> {
-        vec![Box::new(ToDraw {
-            drawable: self.sprite,
-            transform: self.transform,
-        })]
+        (
+            ToDraw {
+                drawable: self.drawable,
+            },
+            self.transform,
+            GlobalTransform::from(self.transform),
+        )
+            .components()
+    }
+
+    fn component_ids() -> Vec<ComponentId> {
+        <(ToDraw, Transform, GlobalTransform)>::component_ids()
+    }
+
+    unsafe fn from_components<'a>(take: &mut dyn FnMut(ComponentId) -> OwningPtr<'a>) -> Self {
+        let (to_draw, transform, _) = <(ToDraw, Transform, GlobalTransform)>::from_components(take);
+        Self {
+            drawable: to_draw.drawable,
+            transform,
+        }
     }
 }
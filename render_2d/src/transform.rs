@@ -1,10 +1,170 @@
-pub use bevy_math::Vec3;
+pub use bevy_math::{Affine2, Mat2, Mat3, UVec2, Vec2, Vec3};
 use ecs::prelude::*;
 
-#[derive(Component, Default, Clone, Copy)]
+/// The transform of an entity relative to its [`Parent`], or to the world if it has none.
+///
+/// The sprite is scaled, then rotated around the transform's position (its top-left corner),
+/// then moved to the position.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
 pub struct Transform {
     /// The X and Y positions are the 2D coordinates of the sprite. The Z position is the depth.
     /// Entities with the lowest Z value will be drawn first, and entities with the highest Z value
     /// will be drawn last.
     pub position: Vec3,
+    /// The rotation in radians. The Y axis points down, so positive angles turn clockwise on
+    /// screen.
+    pub rotation: f32,
+    /// The scale along each axis, before the rotation. Negative values mirror the sprite.
+    pub scale: Vec2,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self {
+        position: Vec3::ZERO,
+        rotation: 0.0,
+        scale: Vec2::ONE,
+    };
+
+    pub fn from_position(position: Vec3) -> Self {
+        Self {
+            position,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: Vec2) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// The 2D affine transform, without the depth.
+    pub fn compute_affine(&self) -> Affine2 {
+        Affine2::from_scale_angle_translation(self.scale, self.rotation, self.position.truncate())
+    }
+
+    /// The 2D transform as a homogeneous matrix, without the depth.
+    pub fn compute_matrix(&self) -> Mat3 {
+        Mat3::from(self.compute_affine())
+    }
+
+    /// The transform `other`, relative to this one, as seen from where this one is relative to.
+    ///
+    /// A rotation after a non-uniform scale can't always be expressed as a [`Transform`], the
+    /// result is then only approximate. [`GlobalTransform::mul_transform`] is exact.
+    pub fn mul_transform(&self, other: Transform) -> Transform {
+        let position = self
+            .compute_affine()
+            .transform_point2(other.position.truncate());
+        Transform {
+            position: position.extend(self.position.z + other.position.z),
+            rotation: self.rotation + other.rotation,
+            scale: self.scale * other.scale,
+        }
+    }
+}
+
+/// The transform of an entity relative to the world, computed from its [`Transform`] and the ones
+/// of its ancestors by [`propagate_transforms`]. Drawing uses it, not the [`Transform`].
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct GlobalTransform {
+    affine: Affine2,
+    depth: f32,
+}
+
+impl Default for GlobalTransform {
+    fn default() -> Self {
+        Self::from(Transform::IDENTITY)
+    }
+}
+
+impl GlobalTransform {
+    /// The position in the world, the Z value being the depth.
+    pub fn position(&self) -> Vec3 {
+        self.affine.translation.extend(self.depth)
+    }
+
+    pub fn affine(&self) -> Affine2 {
+        self.affine
+    }
+
+    /// The 2D transform as a homogeneous matrix, without the depth.
+    pub fn compute_matrix(&self) -> Mat3 {
+        Mat3::from(self.affine)
+    }
+
+    /// The global transform of a child with the local `transform`.
+    pub fn mul_transform(&self, transform: Transform) -> GlobalTransform {
+        GlobalTransform {
+            affine: self.affine * transform.compute_affine(),
+            depth: self.depth + transform.position.z,
+        }
+    }
+
+    /// Map a point from the entity's local space to the world.
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        self.affine.transform_point2(point)
+    }
+}
+
+impl From<Transform> for GlobalTransform {
+    fn from(transform: Transform) -> Self {
+        Self {
+            affine: transform.compute_affine(),
+            depth: transform.position.z,
+        }
+    }
+}
+
+/// Compute the [`GlobalTransform`] of every entity with a [`Transform`], from the root entities
+/// down to their descendants. Runs in [`PostUpdate`].
+pub fn propagate_transforms(
+    roots: Query<(Entity, &Transform), Without<Parent>>,
+    transforms: Query<&Transform>,
+    children: Query<&Children>,
+    mut globals: Query<&mut GlobalTransform>,
+) {
+    for (root, transform) in roots.into_iter() {
+        let global = GlobalTransform::from(*transform);
+        propagate(root, global, &transforms, &children, &mut globals);
+    }
+}
+
+fn propagate(
+    entity: Entity,
+    global: GlobalTransform,
+    transforms: &Query<&Transform>,
+    children: &Query<&Children>,
+    globals: &mut Query<&mut GlobalTransform>,
+) {
+    if let Ok(mut entity_global) = globals.get_mut(entity) {
+        if *entity_global != global {
+            *entity_global = global;
+        }
+    }
+    let Ok(entity_children) = children.get(entity) else {
+        return;
+    };
+    for &child in entity_children.iter() {
+        // Children without a transform don't move with their parent, nor do their descendants.
+        if let Ok(transform) = transforms.get(child) {
+            propagate(
+                child,
+                global.mul_transform(*transform),
+                transforms,
+                children,
+                globals,
+            );
+        }
+    }
 }
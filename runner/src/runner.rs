@@ -35,8 +35,8 @@ pub(crate) fn winit_runner(mut world: World) -> World {
     println!("Starting event loop!");
     let _ = event_loop.run(move |event, _, control_flow| {
         if let Event::RedrawRequested(_) = event {
-            world.run_schedule::<Render>();
-            if let Err(err) = world.get_resource::<SurfaceBuffer>().unwrap().render() {
+            world.run_schedule(Render);
+            if let Err(err) = world.get_resource_mut::<SurfaceBuffer>().unwrap().present() {
                 control_flow.set_exit();
                 log::error!("Error rendering: {}", err);
             }
@@ -0,0 +1,65 @@
+use oxigen::prelude::*;
+
+const GREY: [u8; 4] = [100, 100, 100, 255];
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn alpha_is_the_default_blend_mode() {
+    assert_eq!(BlendMode::default(), BlendMode::Alpha);
+}
+
+#[test]
+fn transparent_pixels_keep_what_is_beneath() {
+    for mode in [BlendMode::Alpha, BlendMode::Additive, BlendMode::Multiply] {
+        assert_eq!(mode.blend(GREY, [255, 0, 0, 0]), GREY);
+    }
+    assert_eq!(
+        BlendMode::Opaque.blend(GREY, [255, 0, 0, 0]),
+        [255, 0, 0, 0]
+    );
+}
+
+#[test]
+fn alpha_blending_is_source_over() {
+    assert_eq!(
+        BlendMode::Alpha.blend(GREY, [200, 0, 50, 255]),
+        [200, 0, 50, 255]
+    );
+    assert_eq!(
+        BlendMode::Alpha.blend(GREY, [200, 0, 50, 128]),
+        [150, 50, 75, 255]
+    );
+    // Over a transparent pixel, the alpha of the sprite is kept.
+    assert_eq!(
+        BlendMode::Alpha.blend([0, 0, 0, 0], [200, 0, 50, 128]),
+        [100, 0, 25, 128]
+    );
+}
+
+#[test]
+fn additive_blending_brightens_and_saturates() {
+    assert_eq!(
+        BlendMode::Additive.blend(GREY, [200, 50, 0, 255]),
+        [255, 150, 100, 255]
+    );
+    assert_eq!(
+        BlendMode::Additive.blend(GREY, [200, 50, 0, 51]),
+        [140, 110, 100, 255]
+    );
+}
+
+#[test]
+fn multiply_blending_darkens() {
+    assert_eq!(
+        BlendMode::Multiply.blend(GREY, [255, 0, 51, 255]),
+        [100, 0, 20, 255]
+    );
+    // Half transparent black halves what's beneath.
+    assert_eq!(
+        BlendMode::Multiply.blend([200, 100, 0, 255], [0, 0, 0, 128]),
+        [100, 50, 0, 255]
+    );
+}
@@ -0,0 +1,122 @@
+use std::{f32::consts::FRAC_PI_2, sync::Arc};
+
+use oxigen::prelude::*;
+
+const WHITE: [u8; 4] = [255, 255, 255, 255];
+const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+fn assert_close(a: Vec2, b: Vec2) {
+    assert!(a.abs_diff_eq(b, 1e-4), "{} != {}", a, b);
+}
+
+fn headless_app(width: usize, height: usize) -> App {
+    let mut app = App::new();
+    app.add_plugin(HeadlessRender2dPlugin::new(width, height))
+        .add_plugin(Render2dPipelinePlugin);
+    app
+}
+
+fn spawn_square(world: &mut World, size: u32, x: f32, y: f32) {
+    let sprite = Sprite::from_image(RgbaImage::from_pixel(size, size, WHITE.into()));
+    world.spawn(
+        SpriteBundle::from_sprite(Arc::new(sprite))
+            .with_transform(Transform::from_position(Vec3::new(x, y, 0.0))),
+    );
+}
+
+/// The white pixels of the rendered frame, row by row.
+fn render(app: &mut App) -> Vec<(u32, u32)> {
+    let world = app.world();
+    world.update();
+    world.run_schedule(Render);
+    let image = world.get_resource::<SurfaceBuffer>().unwrap().to_image();
+    image
+        .enumerate_pixels()
+        .filter(|(.., pixel)| pixel.0 != BLACK)
+        .map(|(x, y, _)| (x, y))
+        .collect()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn camera_position_is_at_the_center_of_the_viewport() {
+    let mut app = headless_app(4, 4);
+    app.world()
+        .spawn(Camera2d::from_position(Vec2::new(10.0, 10.0)));
+    spawn_square(app.world(), 2, 9.0, 9.0);
+
+    assert_eq!(render(&mut app), vec![(1, 1), (2, 1), (1, 2), (2, 2)]);
+}
+
+#[test]
+fn zoom_scales_the_world_around_the_camera() {
+    let mut app = headless_app(4, 4);
+    app.world().spawn(Camera2d::default().with_zoom(2.0));
+    spawn_square(app.world(), 1, 0.0, 0.0);
+
+    assert_eq!(render(&mut app), vec![(2, 2), (3, 2), (2, 3), (3, 3)]);
+}
+
+#[test]
+fn cameras_draw_in_their_viewport_only() {
+    let mut app = headless_app(4, 2);
+    let half = UVec2::new(2, 2);
+    app.world()
+        .spawn(Camera2d::default().with_viewport(Viewport::new(UVec2::ZERO, half)));
+    app.world()
+        .spawn(Camera2d::default().with_viewport(Viewport::new(UVec2::new(2, 0), half)));
+    spawn_square(app.world(), 2, 0.0, -1.0);
+
+    // Both cameras show the left column of the square in the middle of their viewport. The right
+    // column is outside of the left viewport, and doesn't spill over the right one.
+    assert_eq!(render(&mut app), vec![(1, 0), (3, 0), (1, 1), (3, 1)]);
+}
+
+#[test]
+fn surface_to_world_inverts_world_to_surface() {
+    let surface = UVec2::new(100, 50);
+    let camera = Camera2d::from_position(Vec2::new(30.0, -20.0))
+        .with_zoom(4.0)
+        .with_rotation(0.3)
+        .with_viewport(Viewport::new(UVec2::new(50, 10), UVec2::new(40, 30)));
+
+    for point in [Vec2::ZERO, Vec2::new(31.5, -19.0), Vec2::new(-7.0, 12.0)] {
+        let on_surface = camera.world_to_surface(surface, point);
+        assert_close(camera.surface_to_world(surface, on_surface), point);
+    }
+    // The center of the viewport is the camera position.
+    assert_close(
+        camera.world_to_surface(surface, camera.position),
+        Vec2::new(70.0, 25.0),
+    );
+}
+
+#[test]
+fn rotated_cameras_turn_the_world_the_other_way() {
+    let surface = UVec2::new(10, 10);
+    let camera = Camera2d::default().with_rotation(FRAC_PI_2);
+
+    // With the camera turned clockwise, what's right of it is above it on screen.
+    assert_close(
+        camera.world_to_surface(surface, Vec2::new(1.0, 0.0)),
+        Vec2::new(5.0, 4.0),
+    );
+    assert_close(
+        camera.surface_to_world(surface, Vec2::new(5.0, 4.0)),
+        Vec2::new(1.0, 0.0),
+    );
+}
+
+#[test]
+fn viewports_are_clipped_to_the_surface() {
+    let camera =
+        Camera2d::default().with_viewport(Viewport::new(UVec2::new(6, 2), UVec2::new(10, 10)));
+
+    let viewport = camera.viewport_rect(UVec2::new(8, 8));
+    assert_eq!(viewport, Viewport::new(UVec2::new(6, 2), UVec2::new(2, 6)));
+    assert!(viewport.contains(Vec2::new(7.5, 2.0)));
+    assert!(!viewport.contains(Vec2::new(8.0, 2.0)));
+}
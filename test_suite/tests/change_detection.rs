@@ -0,0 +1,164 @@
+use oxigen::prelude::*;
+
+#[derive(Component, Debug, PartialEq)]
+struct Position(i32);
+
+#[derive(Component)]
+struct Player;
+
+#[derive(Component, Debug, PartialEq)]
+#[component(storage = "SparseSet")]
+struct Health(u32);
+
+#[derive(Resource, Default)]
+struct Seen {
+    added: Vec<Entity>,
+    changed: Vec<Entity>,
+}
+
+#[derive(Resource, Default)]
+struct Score(u32);
+
+#[derive(Resource, Default)]
+struct ScoreChanges(u32);
+
+fn move_players(query: Query<&mut Position, With<Player>>) {
+    for mut position in query {
+        position.0 += 1;
+    }
+}
+
+fn record_changes(
+    added: Query<Entity, Added<Position>>,
+    changed: Query<Entity, Changed<Position>>,
+    mut seen: ResMut<Seen>,
+) {
+    seen.added = added.into_iter().collect();
+    seen.changed = changed.into_iter().collect();
+}
+
+fn count_score_changes(score: Res<Score>, mut changes: ResMut<ScoreChanges>) {
+    if score.is_changed() {
+        changes.0 += 1;
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn added_and_changed_filters_only_see_new_changes() {
+    let mut world = World::new();
+    world.init_resource::<Seen>();
+    world.add_systems(Update, (move_players, record_changes));
+    let player = world.spawn((Position(0), Player)).id();
+    let rock = world.spawn(Position(5)).id();
+
+    world.update();
+    let seen = world.get_resource::<Seen>().unwrap();
+    assert_eq!(seen.added, vec![player, rock]);
+    assert_eq!(seen.changed, vec![player, rock]);
+    drop(seen);
+
+    world.update();
+    let seen = world.get_resource::<Seen>().unwrap();
+    assert!(seen.added.is_empty());
+    assert_eq!(seen.changed, vec![player]);
+    drop(seen);
+
+    let new_rock = world.spawn(Position(7)).id();
+    world.get_component_mut::<Position>(rock).unwrap().0 = 6;
+    world.update();
+    let seen = world.get_resource::<Seen>().unwrap();
+    assert_eq!(seen.added, vec![new_rock]);
+    assert_eq!(seen.changed, vec![player, rock, new_rock]);
+}
+
+#[test]
+fn reading_through_mut_doesnt_mark_as_changed() {
+    let mut world = World::new();
+    let entity = world.spawn((Position(0), Health(10))).id();
+    world.update();
+
+    for (position, health) in world.query::<(&mut Position, &mut Health)>() {
+        assert!(!position.is_changed());
+        assert_eq!(health.0, 10);
+    }
+    assert_eq!(
+        world
+            .query_filtered::<Entity, Changed<Position>>()
+            .into_iter()
+            .count(),
+        0
+    );
+    assert_eq!(
+        world
+            .query_filtered::<Entity, Changed<Health>>()
+            .into_iter()
+            .count(),
+        0
+    );
+
+    for mut health in world.query::<&mut Health>() {
+        health.0 -= 1;
+    }
+    let query = world.query::<Ref<Health>>();
+    let health = query.get(entity).unwrap();
+    assert!(health.is_changed());
+    assert!(!health.is_added());
+    assert_eq!(
+        world
+            .query_filtered::<Entity, Or<(Changed<Position>, Changed<Health>)>>()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec![entity]
+    );
+}
+
+#[test]
+fn resource_changes_are_detected_per_system() {
+    let mut world = World::new();
+    world.init_resource::<Score>();
+    world.init_resource::<ScoreChanges>();
+    world.add_systems(Update, count_score_changes);
+
+    world.update();
+    world.update();
+    assert_eq!(world.get_resource::<ScoreChanges>().unwrap().0, 1);
+
+    world.get_resource_mut::<Score>().unwrap().0 += 1;
+    world.update();
+    world.update();
+    assert_eq!(world.get_resource::<ScoreChanges>().unwrap().0, 2);
+}
+
+#[test]
+fn old_changes_dont_look_new_after_the_tick_wraps_around() {
+    let mut world = World::new();
+    world.init_resource::<Seen>();
+    let entity = world.spawn(Position(0)).id();
+    let spawn_tick = world.change_tick().get();
+    world.advance_change_tick(1000);
+    let record = world.register_system(record_changes);
+    world.run_system_by_id(record).unwrap();
+    assert_eq!(world.get_resource::<Seen>().unwrap().added, vec![entity]);
+
+    // Run until the change tick wraps around to between the spawn and the last run of the system.
+    let target = (1u64 << 32) + u64::from(spawn_tick) + 500;
+    let mut ticks = u64::from(world.change_tick().get());
+    while ticks < target {
+        let step = (target - ticks).min(u64::from(CHECK_TICK_THRESHOLD)) as u32;
+        let before = world.change_tick().get();
+        world.advance_change_tick(step);
+        world.update();
+        ticks += u64::from(world.change_tick().get().wrapping_sub(before));
+    }
+    let this_run = world.change_tick().get();
+    assert!(this_run > spawn_tick && this_run < spawn_tick + 1000);
+
+    world.run_system_by_id(record).unwrap();
+    let seen = world.get_resource::<Seen>().unwrap();
+    assert!(seen.added.is_empty());
+    assert!(seen.changed.is_empty());
+}
@@ -0,0 +1,60 @@
+use oxigen::prelude::*;
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+fn app_with_clear_color(clear_color: ClearColor) -> App {
+    let mut app = App::new();
+    app.insert_resource(clear_color)
+        .add_plugin(HeadlessRender2dPlugin::new(3, 2))
+        .add_plugin(Render2dPipelinePlugin);
+    app
+}
+
+/// Draw a red pixel at the top-left corner of the frame, outside of `Render`.
+fn draw_red_pixel(world: &mut World) {
+    let sprite = Sprite::from_image(RgbaImage::from_pixel(1, 1, RED.into()));
+    world
+        .get_resource_mut::<SurfaceBuffer>()
+        .unwrap()
+        .frame()
+        .draw(
+            &sprite,
+            Affine2::IDENTITY,
+            Sampling::Nearest,
+            BlendMode::Opaque,
+        );
+}
+
+fn frame(world: &World) -> RgbaImage {
+    world.get_resource::<SurfaceBuffer>().unwrap().to_image()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn render_clears_the_frame_to_the_clear_color() {
+    let mut app = app_with_clear_color(ClearColor::Color(BLUE));
+    let world = app.world();
+    draw_red_pixel(world);
+    assert_eq!(frame(world).get_pixel(0, 0).0, RED);
+
+    world.run_schedule(Render);
+
+    assert!(frame(world).pixels().all(|pixel| pixel.0 == BLUE));
+}
+
+#[test]
+fn disabled_clear_color_keeps_the_previous_frame() {
+    let mut app = app_with_clear_color(ClearColor::Disabled);
+    let world = app.world();
+    draw_red_pixel(world);
+    let before = frame(world);
+
+    world.run_schedule(Render);
+
+    assert_eq!(frame(world), before);
+    assert_eq!(frame(world).get_pixel(0, 0).0, RED);
+}
@@ -0,0 +1,116 @@
+use oxigen::prelude::*;
+
+const SIZE: usize = 4;
+const EMPTY: [u8; 4] = [0, 0, 0, 0];
+
+/// A 3x2 sprite whose pixels all differ: `[10 * x, 10 * y, 100, 255]`.
+fn sprite() -> Sprite {
+    Sprite::from_image(RgbaImage::from_fn(3, 2, |x, y| {
+        [10 * x as u8, 10 * y as u8, 100, 255].into()
+    }))
+}
+
+fn draw_at(sprite: &Sprite, x: f32, y: f32, blend_mode: BlendMode) -> Vec<u8> {
+    let mut pixels = vec![0; SIZE * SIZE * 4];
+    let affine = Transform::from_position(Vec3::new(x, y, 0.0)).compute_affine();
+    Frame::new(&mut pixels, SIZE, SIZE).draw(sprite, affine, Sampling::Nearest, blend_mode);
+    pixels
+}
+
+fn pixel(pixels: &mut [u8], x: usize, y: usize) -> [u8; 4] {
+    Frame::new(pixels, SIZE, SIZE).pixel(x, y).unwrap()
+}
+
+/// What the frame should look like, computed pixel by pixel.
+fn expected(sprite: &Sprite, x: i64, y: i64) -> Vec<u8> {
+    let mut pixels = Vec::new();
+    for row in 0..SIZE as i64 {
+        for column in 0..SIZE as i64 {
+            let (u, v) = (column - x, row - y);
+            let inside =
+                (0..sprite.width() as i64).contains(&u) && (0..sprite.height() as i64).contains(&v);
+            let color = if inside {
+                sprite.buffer().get_pixel(u as u32, v as u32).0
+            } else {
+                EMPTY
+            };
+            pixels.extend(color);
+        }
+    }
+    pixels
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn sprites_past_the_top_left_corner_are_clipped() {
+    let mut pixels = draw_at(&sprite(), -1.0, -1.0, BlendMode::Opaque);
+
+    // Only the bottom row, without its first pixel, is left.
+    assert_eq!(pixel(&mut pixels, 0, 0), [10, 10, 100, 255]);
+    assert_eq!(pixel(&mut pixels, 1, 0), [20, 10, 100, 255]);
+    assert_eq!(pixel(&mut pixels, 2, 0), EMPTY);
+    assert_eq!(pixel(&mut pixels, 0, 1), EMPTY);
+}
+
+#[test]
+fn sprites_past_the_bottom_right_corner_are_clipped() {
+    let mut pixels = draw_at(&sprite(), 2.0, 3.0, BlendMode::Opaque);
+
+    // Only the first two pixels of the top row are left.
+    assert_eq!(pixel(&mut pixels, 2, 3), [0, 0, 100, 255]);
+    assert_eq!(pixel(&mut pixels, 3, 3), [10, 0, 100, 255]);
+    assert_eq!(pixel(&mut pixels, 1, 3), EMPTY);
+    assert_eq!(pixel(&mut pixels, 3, 2), EMPTY);
+}
+
+#[test]
+fn sprites_entirely_off_screen_draw_nothing() {
+    for (x, y) in [
+        (-3.0, 0.0),
+        (0.0, -2.0),
+        (4.0, 0.0),
+        (0.0, 4.0),
+        (-50.0, 50.0),
+    ] {
+        for blend_mode in [BlendMode::Opaque, BlendMode::Alpha] {
+            assert!(draw_at(&sprite(), x, y, blend_mode)
+                .iter()
+                .all(|&byte| byte == 0));
+        }
+    }
+}
+
+#[test]
+fn sprites_larger_than_the_frame_are_clipped_on_all_sides() {
+    let big = Sprite::from_image(RgbaImage::from_fn(8, 8, |x, y| {
+        [x as u8, y as u8, 0, 255].into()
+    }));
+
+    for blend_mode in [BlendMode::Opaque, BlendMode::Alpha] {
+        assert_eq!(
+            draw_at(&big, -2.0, -3.0, blend_mode),
+            expected(&big, -2, -3)
+        );
+    }
+}
+
+#[test]
+fn clipping_matches_per_pixel_drawing_at_every_offset() {
+    let sprite = sprite();
+    for y in -3..=5 {
+        for x in -4..=5 {
+            let expected = expected(&sprite, x, y);
+            // Opaque sprites are copied row by row, the others are blended pixel by pixel.
+            for blend_mode in [BlendMode::Opaque, BlendMode::Alpha] {
+                assert_eq!(
+                    draw_at(&sprite, x as f32, y as f32, blend_mode),
+                    expected,
+                    "{blend_mode:?} sprite at ({x}, {y})"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use oxigen::prelude::*;
+
+#[derive(Component, Debug, PartialEq)]
+struct Health(u32);
+
+#[derive(Component)]
+struct Dead;
+
+#[derive(Resource, Default)]
+struct Spawned(Vec<Entity>);
+
+#[derive(Resource, Debug, PartialEq)]
+struct Wave(u32);
+
+fn spawn_enemies(mut commands: Commands, mut spawned: ResMut<Spawned>) {
+    for health in 1..=3 {
+        let entity = commands.spawn(Health(health)).id();
+        spawned.0.push(entity);
+    }
+    commands.insert_resource(Wave(1));
+}
+
+fn kill_weak(mut commands: Commands, query: Query<(Entity, &Health), Without<Dead>>) {
+    for (entity, health) in query {
+        if health.0 < 2 {
+            commands.entity(entity).insert(Dead);
+        }
+    }
+}
+
+fn despawn_dead(mut commands: Commands, query: Query<Entity, With<Dead>>) {
+    for entity in query {
+        commands.entity(entity).despawn();
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn commands_are_applied_after_each_system() {
+    let mut world = World::new();
+    world.init_resource::<Spawned>();
+    world.add_systems(Update, (spawn_enemies, kill_weak, despawn_dead));
+
+    world.update();
+
+    let spawned = world.get_resource::<Spawned>().unwrap().0.clone();
+    assert_eq!(spawned.len(), 3);
+    assert!(!world.contains_entity(spawned[0]));
+    assert_eq!(world.get_component::<Health>(spawned[1]), Some(&Health(2)));
+    assert_eq!(world.get_component::<Health>(spawned[2]), Some(&Health(3)));
+    assert_eq!(*world.get_resource::<Wave>().unwrap(), Wave(1));
+    assert_eq!(world.entity_count(), 2);
+}
+
+#[test]
+fn reserved_entities_reuse_despawned_indices() {
+    let mut world = World::new();
+    let old = world.spawn(Health(1)).id();
+    world.despawn(old);
+
+    world.run_system(|mut commands: Commands| {
+        let reused = commands.spawn_empty().id();
+        let new = commands.spawn(Health(2)).id();
+        commands.add(move |world: &mut World| {
+            assert!(world.contains_entity(reused));
+            world.entity(new).insert(Dead);
+        });
+    });
+
+    let entities = world.all_entities().collect::<Vec<_>>();
+    assert_eq!(entities.len(), 2);
+    assert_eq!(entities[0].index(), old.index());
+    assert_ne!(entities[0], old);
+    assert_eq!(
+        world
+            .query_filtered::<&Health, With<Dead>>()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec![&Health(2)]
+    );
+}
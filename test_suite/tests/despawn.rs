@@ -0,0 +1,119 @@
+use oxigen::prelude::*;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Counts how many times it was dropped.
+#[derive(Component)]
+struct DropCounter(Arc<AtomicUsize>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Component, Debug, PartialEq)]
+struct Health(u32);
+
+#[derive(Component, Debug, PartialEq)]
+struct Name(String);
+
+fn drops(counter: &Arc<AtomicUsize>) -> usize {
+    counter.load(Ordering::SeqCst)
+}
+
+#[test]
+fn despawn_drops_every_component_once() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let mut world = World::new();
+    let a = world.spawn(DropCounter(counter.clone())).id();
+    let b = world.spawn((DropCounter(counter.clone()), Health(10))).id();
+    let c = world.spawn(DropCounter(counter.clone())).id();
+
+    assert!(world.despawn(a));
+    assert_eq!(drops(&counter), 1);
+    assert!(!world.despawn(a));
+    assert_eq!(drops(&counter), 1);
+
+    world.entity(b).despawn();
+    assert_eq!(drops(&counter), 2);
+    assert!(world.get_component::<Health>(b).is_none());
+
+    // `c` was swapped into the removed rows, it must still be reachable.
+    assert!(world.get_component::<DropCounter>(c).is_some());
+    assert_eq!(world.entity_count(), 1);
+
+    drop(world);
+    assert_eq!(drops(&counter), 3);
+}
+
+#[test]
+fn remove_drops_and_keeps_other_rows_valid() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let mut world = World::new();
+    let entities: Vec<Entity> = (0..4)
+        .map(|i| world.spawn((DropCounter(counter.clone()), Health(i))).id())
+        .collect();
+
+    world.entity(entities[0]).remove::<(DropCounter, Health)>();
+    assert_eq!(drops(&counter), 1);
+    assert!(world.get_component::<Health>(entities[0]).is_none());
+    assert!(world.contains_entity(entities[0]));
+
+    // Removing a component the entity doesn't have does nothing.
+    world.entity(entities[0]).remove::<DropCounter>();
+    assert_eq!(drops(&counter), 1);
+
+    for (i, &entity) in entities.iter().enumerate().skip(1) {
+        assert_eq!(
+            world.get_component::<Health>(entity),
+            Some(&Health(i as u32))
+        );
+    }
+}
+
+#[test]
+fn take_moves_components_out_without_dropping() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let mut world = World::new();
+    let first = world
+        .spawn((DropCounter(counter.clone()), Name("first".to_string())))
+        .id();
+    let second = world
+        .spawn((DropCounter(counter.clone()), Name("second".to_string())))
+        .id();
+
+    let (taken_counter, name) = world.entity(first).take::<(DropCounter, Name)>().unwrap();
+    assert_eq!(name, Name("first".to_string()));
+    assert_eq!(drops(&counter), 0);
+    drop(taken_counter);
+    assert_eq!(drops(&counter), 1);
+
+    // The entity doesn't have the bundle anymore.
+    assert!(world.entity(first).take::<Name>().is_none());
+    assert_eq!(
+        world.get_component::<Name>(second),
+        Some(&Name("second".to_string()))
+    );
+
+    drop(world);
+    assert_eq!(drops(&counter), 2);
+}
+
+#[test]
+fn stale_entities_are_detected() {
+    let mut world = World::new();
+    let old = world.spawn(Health(1)).id();
+    world.despawn(old);
+    let new = world.spawn(Health(2)).id();
+
+    assert_eq!(old.index(), new.index());
+    assert_ne!(old, new);
+    assert!(!world.contains_entity(old));
+    assert!(world.get_component::<Health>(old).is_none());
+    assert_eq!(world.get_component::<Health>(new), Some(&Health(2)));
+    assert!(world.query::<&Health>().get(old).is_err());
+    assert!(world.query::<&Health>().get(new).is_ok());
+}
@@ -0,0 +1,21 @@
+use oxigen::prelude::*;
+
+#[derive(Component)]
+struct Position(i32);
+
+#[test]
+fn entity_ids_are_dense_and_deterministic() {
+    let mut world = World::new();
+    let entities: Vec<Entity> = (0..3).map(|i| world.spawn(Position(i)).id()).collect();
+
+    for (i, entity) in entities.iter().enumerate() {
+        assert_eq!(entity.index(), i as u32);
+        assert_eq!(entity.generation(), 0);
+        assert_eq!(
+            world.get_component::<Position>(*entity).unwrap().0,
+            i as i32
+        );
+    }
+    assert_eq!(world.all_entities().collect::<Vec<_>>(), entities);
+    assert_eq!(world.entity_count(), 3);
+}
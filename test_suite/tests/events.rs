@@ -0,0 +1,84 @@
+use oxigen::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Damage(u32);
+
+#[derive(Resource, Default)]
+struct Received(Vec<u32>);
+
+#[derive(Resource, Default)]
+struct ReceivedLate(Vec<u32>);
+
+fn read_late(mut reader: EventReader<Damage>, mut received: ResMut<ReceivedLate>) {
+    received.0.extend(reader.read().map(|damage| damage.0));
+}
+
+fn deal_damage(mut writer: EventWriter<Damage>) {
+    writer.send_batch([Damage(1), Damage(2)]);
+}
+
+fn read_damage(mut reader: EventReader<Damage>, mut received: ResMut<Received>) {
+    received.0.extend(reader.read().map(|damage| damage.0));
+    assert_eq!(reader.read().count(), 0);
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn every_reader_sees_each_event_once() {
+    let mut world = World::new();
+    world.add_event::<Damage>();
+    world.init_resource::<Received>();
+    world.init_resource::<ReceivedLate>();
+    // `read_late` runs before the writer, so it only sees the events on the next update.
+    world.add_systems(Update, (read_late, deal_damage, read_damage));
+
+    world.update();
+    assert_eq!(world.get_resource::<Received>().unwrap().0, vec![1, 2]);
+    assert!(world.get_resource::<ReceivedLate>().unwrap().0.is_empty());
+
+    world.update();
+    assert_eq!(
+        world.get_resource::<Received>().unwrap().0,
+        vec![1, 2, 1, 2]
+    );
+    assert_eq!(world.get_resource::<ReceivedLate>().unwrap().0, vec![1, 2]);
+}
+
+#[test]
+fn events_are_dropped_after_two_updates() {
+    let mut world = World::new();
+    world.add_event::<Damage>();
+    world.send_event(Damage(7));
+
+    world.update();
+    assert_eq!(world.get_resource::<Events<Damage>>().unwrap().len(), 1);
+    world.update();
+    assert!(world.get_resource::<Events<Damage>>().unwrap().is_empty());
+}
+
+#[test]
+fn readers_of_the_same_event_dont_conflict() {
+    let mut world = World::new();
+    world.add_event::<Damage>();
+    world.send_event(Damage(3));
+
+    world.run_system(
+        |mut first: EventReader<Damage>, mut second: EventReader<Damage>| {
+            assert_eq!(first.read().cloned().collect::<Vec<_>>(), vec![Damage(3)]);
+            assert_eq!(second.len(), 1);
+            second.clear();
+            assert!(second.is_empty());
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "conflicting access")]
+fn reading_and_writing_the_same_event_conflicts() {
+    let mut world = World::new();
+    world.add_event::<Damage>();
+    world.run_system(|_reader: EventReader<Damage>, _writer: EventWriter<Damage>| {});
+}
@@ -0,0 +1,103 @@
+use oxigen::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+#[derive(Resource, Default)]
+struct Rendezvous {
+    first: AtomicBool,
+    second: AtomicBool,
+    first_met: AtomicBool,
+    second_met: AtomicBool,
+}
+
+#[derive(Resource, Default)]
+struct Log(Vec<&'static str>);
+
+#[derive(Component)]
+struct Marker;
+
+/// Signal `mine`, and wait (for a while) until the other system signals `theirs`.
+fn meet(mine: &AtomicBool, theirs: &AtomicBool) -> bool {
+    mine.store(true, Ordering::SeqCst);
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(1) {
+        if theirs.load(Ordering::SeqCst) {
+            return true;
+        }
+        std::thread::yield_now();
+    }
+    false
+}
+
+fn first(rendezvous: Res<Rendezvous>) {
+    let met = meet(&rendezvous.first, &rendezvous.second);
+    rendezvous.first_met.store(met, Ordering::SeqCst);
+}
+
+fn second(rendezvous: Res<Rendezvous>) {
+    let met = meet(&rendezvous.second, &rendezvous.first);
+    rendezvous.second_met.store(met, Ordering::SeqCst);
+}
+
+fn log_a(mut log: ResMut<Log>) {
+    log.0.push("a");
+}
+
+fn log_b(mut log: ResMut<Log>) {
+    log.0.push("b");
+}
+
+fn log_entities(world: &mut World) {
+    let count = world.query::<&Marker>().into_iter().len();
+    world
+        .get_pure_resource_mut::<Log>()
+        .unwrap()
+        .0
+        .push(if count == 1 { "world" } else { "?" });
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn non_conflicting_systems_run_concurrently() {
+    let mut world = World::new();
+    world.init_resource::<Rendezvous>();
+    world.add_systems(Update, (first, second));
+
+    world.update();
+
+    let rendezvous = world.get_resource::<Rendezvous>().unwrap();
+    assert!(rendezvous.first.load(Ordering::SeqCst) && rendezvous.second.load(Ordering::SeqCst));
+    // The thread pool has a single thread on single core machines.
+    if std::thread::available_parallelism().map_or(1, |n| n.get()) > 1 {
+        assert!(rendezvous.first_met.load(Ordering::SeqCst));
+        assert!(rendezvous.second_met.load(Ordering::SeqCst));
+    }
+}
+
+#[test]
+fn conflicting_systems_keep_their_order() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_systems(
+        Update,
+        (
+            log_b,
+            log_a,
+            |mut commands: Commands| {
+                commands.spawn(Marker);
+            },
+            log_entities,
+            log_b,
+        ),
+    );
+
+    world.update();
+
+    assert_eq!(
+        world.get_resource::<Log>().unwrap().0,
+        vec!["b", "a", "world", "b"]
+    );
+}
@@ -0,0 +1,155 @@
+use oxigen::prelude::*;
+
+#[derive(Resource, Default)]
+struct Counter(u32);
+
+#[derive(Resource)]
+struct Missing;
+
+#[derive(Debug)]
+struct TooHigh(u32);
+
+impl std::fmt::Display for TooHigh {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the counter is too high: {}", self.0)
+    }
+}
+
+impl std::error::Error for TooHigh {}
+
+fn count_to_two(mut counter: ResMut<Counter>) -> Result<(), TooHigh> {
+    counter.0 += 1;
+    if counter.0 > 2 {
+        return Err(TooHigh(counter.0));
+    }
+    Ok(())
+}
+
+fn fail_with_message() -> Result<(), String> {
+    Err("no luck".to_string())
+}
+
+fn needs_missing(_missing: Res<Missing>, mut counter: ResMut<Counter>) {
+    counter.0 += 1;
+}
+
+fn missing_exists(_missing: Res<Missing>) -> bool {
+    true
+}
+
+fn count(mut counter: ResMut<Counter>) {
+    counter.0 += 1;
+}
+
+fn counter(world: &World) -> u32 {
+    world.get_resource::<Counter>().unwrap().0
+}
+
+fn error_messages(world: &World) -> Vec<String> {
+    world
+        .get_resource::<Errors>()
+        .map(|errors| errors.0.iter().map(ToString::to_string).collect())
+        .unwrap_or_default()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn returned_errors_are_collected() {
+    let mut world = World::new();
+    world.set_error_handler(collect_errors);
+    world.init_resource::<Counter>();
+    world.add_systems(Update, count_to_two);
+
+    world.update();
+    world.update();
+    assert!(error_messages(&world).is_empty());
+
+    world.update();
+    let errors = world.get_resource::<Errors>().unwrap();
+    assert_eq!(errors.0.len(), 1);
+    assert!(errors.0[0].system().contains("count_to_two"));
+    assert!(matches!(errors.0[0], SystemError::Failed { .. }));
+    assert!(errors.0[0]
+        .to_string()
+        .ends_with("failed: the counter is too high: 3"));
+}
+
+#[test]
+fn any_error_type_can_be_returned() {
+    let mut world = World::new();
+    world.set_error_handler(collect_errors);
+
+    world.run_system(fail_with_message);
+
+    let messages = error_messages(&world);
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("fail_with_message"));
+    assert!(messages[0].ends_with("failed: no luck"));
+}
+
+#[test]
+fn missing_resources_are_named_errors() {
+    let mut world = World::new();
+    world.set_error_handler(collect_errors);
+    world.init_resource::<Counter>();
+    world.add_systems(Update, (needs_missing, count));
+
+    world.update();
+
+    // The system didn't run, the others still did.
+    assert_eq!(counter(&world), 1);
+    let errors = world.get_resource::<Errors>().unwrap();
+    assert_eq!(errors.0.len(), 1);
+    match &errors.0[0] {
+        SystemError::Param {
+            system,
+            error: ParamError::MissingResource(resource),
+        } => {
+            assert!(system.contains("needs_missing"));
+            assert!(resource.ends_with("Missing"));
+        }
+        error => panic!("Unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn missing_resources_of_conditions_are_errors() {
+    let mut world = World::new();
+    world.set_error_handler(collect_errors);
+    world.init_resource::<Counter>();
+    world.add_systems(Update, count.run_if(missing_exists));
+
+    world.update();
+
+    assert_eq!(counter(&world), 0);
+    let messages = error_messages(&world);
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("missing_exists"));
+}
+
+#[test]
+#[should_panic(expected = "couldn't run: the resource")]
+fn errors_panic_by_default() {
+    let mut world = World::new();
+    world.init_resource::<Counter>();
+    world.run_system(needs_missing);
+}
+
+#[test]
+fn logged_errors_dont_stop_the_app() {
+    let mut app = App::new();
+    app.set_error_handler(log_error)
+        .init_resource::<Counter>()
+        .add_systems(Update, (count_to_two, needs_missing));
+
+    let world = app.world();
+    for _ in 0..4 {
+        world.update();
+    }
+
+    assert_eq!(counter(world), 4);
+    assert!(!world.contains_resource::<Errors>());
+}
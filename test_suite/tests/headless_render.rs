@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use oxigen::prelude::*;
+
+const GREY: [u8; 4] = [50, 50, 50, 255];
+
+fn square(size: u32, color: [u8; 4]) -> Arc<Sprite> {
+    Arc::new(Sprite::from_image(RgbaImage::from_pixel(
+        size,
+        size,
+        color.into(),
+    )))
+}
+
+fn headless_app(width: usize, height: usize) -> App {
+    let mut app = App::new();
+    app.insert_resource(ClearColor::Color(GREY))
+        .add_plugin(HeadlessRender2dPlugin::new(width, height))
+        .add_plugin(Render2dPipelinePlugin);
+    app
+}
+
+fn render(app: &mut App) -> RgbaImage {
+    let world = app.world();
+    world.update();
+    world.run_schedule(Render);
+    world.get_resource::<SurfaceBuffer>().unwrap().to_image()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn headless_surface_has_the_requested_size() {
+    let mut app = headless_app(7, 3);
+    {
+        let surface = app.world().get_resource::<SurfaceBuffer>().unwrap();
+        assert_eq!((surface.width(), surface.height()), (7, 3));
+    }
+
+    let image = render(&mut app);
+    assert_eq!(image.dimensions(), (7, 3));
+    assert!(image.pixels().all(|pixel| pixel.0 == GREY));
+}
+
+#[test]
+fn rendered_frame_matches_the_golden_image() {
+    let mut app = headless_app(5, 5);
+    let world = app.world();
+    world.spawn(
+        SpriteBundle::from_sprite(square(2, [255, 0, 0, 255]))
+            .with_transform(Transform::from_position(Vec3::new(1.0, 1.0, 0.0))),
+    );
+    // Drawn over the red square, though spawned after.
+    world.spawn(
+        SpriteBundle::from_sprite(square(3, [0, 0, 255, 128]))
+            .with_transform(Transform::from_position(Vec3::new(2.0, 2.0, 1.0))),
+    );
+
+    let image = render(&mut app);
+
+    let golden = RgbaImage::from_fn(5, 5, |x, y| {
+        let red = (1..3).contains(&x) && (1..3).contains(&y);
+        let blue = (2..5).contains(&x) && (2..5).contains(&y);
+        match (red, blue) {
+            (true, true) => [127, 0, 128, 255],
+            (false, true) => [25, 25, 153, 255],
+            (true, false) => [255, 0, 0, 255],
+            (false, false) => GREY,
+        }
+        .into()
+    });
+    assert_eq!(image, golden);
+}
+
+#[test]
+fn frames_can_be_read_back_after_every_render() {
+    let mut app = headless_app(4, 1);
+    app.insert_resource(ClearColor::Disabled);
+    let sprite = app
+        .world()
+        .spawn(SpriteBundle::from_sprite(square(1, [255, 255, 255, 255])))
+        .id();
+
+    let first = render(&mut app);
+    app.world()
+        .get_component_mut::<Transform>(sprite)
+        .unwrap()
+        .position
+        .x = 2.0;
+    let second = render(&mut app);
+
+    assert_eq!(first.get_pixel(0, 0).0, [255, 255, 255, 255]);
+    assert_eq!(first.get_pixel(2, 0).0, [0, 0, 0, 0]);
+    // Without clearing, the sprite leaves a trail.
+    assert_eq!(second.get_pixel(0, 0).0, [255, 255, 255, 255]);
+    assert_eq!(second.get_pixel(2, 0).0, [255, 255, 255, 255]);
+}
@@ -0,0 +1,203 @@
+use oxigen::prelude::*;
+
+#[derive(Component)]
+struct Node;
+
+fn transform_at(x: f32, y: f32) -> (Transform, GlobalTransform) {
+    let transform = Transform::from_position(Vec3::new(x, y, 0.0));
+    (transform, GlobalTransform::from(transform))
+}
+
+fn world_with_propagation() -> World {
+    let mut world = World::new();
+    world.add_systems(PostUpdate, propagate_transforms);
+    world
+}
+
+fn global_position(world: &World, entity: Entity) -> Vec3 {
+    world
+        .get_component::<GlobalTransform>(entity)
+        .unwrap()
+        .position()
+}
+
+fn children(world: &World, entity: Entity) -> Vec<Entity> {
+    world
+        .get_component::<Children>(entity)
+        .map(|children| children.to_vec())
+        .unwrap_or_default()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn with_children_links_both_ways() {
+    let mut world = World::new();
+    let mut spawned = Vec::new();
+    let parent = world
+        .spawn(Node)
+        .with_children(|parent| {
+            spawned.push(parent.spawn(Node).id());
+            spawned.push(parent.spawn(Node).id());
+        })
+        .id();
+
+    assert_eq!(children(&world, parent), spawned);
+    for child in spawned {
+        assert_eq!(world.get_component::<Parent>(child).unwrap().get(), parent);
+    }
+}
+
+#[test]
+fn set_parent_moves_the_child() {
+    let mut world = World::new();
+    let first = world.spawn(Node).id();
+    let second = world.spawn(Node).id();
+    let child = world.spawn(Node).id();
+
+    world.entity(child).set_parent(first);
+    world.entity(child).set_parent(second);
+
+    assert!(world.get_component::<Children>(first).is_none());
+    assert_eq!(children(&world, second), vec![child]);
+
+    world.entity(child).remove_parent();
+    assert!(world.get_component::<Parent>(child).is_none());
+    assert!(world.get_component::<Children>(second).is_none());
+}
+
+#[test]
+#[should_panic(expected = "can't be the parent of its ancestor")]
+fn parents_cant_form_a_cycle() {
+    let mut world = World::new();
+    let grandparent = world.spawn(Node).id();
+    let parent = world.spawn(Node).id();
+    let child = world.spawn(Node).id();
+    world.entity(parent).set_parent(grandparent);
+    world.entity(child).set_parent(parent);
+
+    world.entity(grandparent).set_parent(child);
+}
+
+#[test]
+fn despawn_recursive_despawns_descendants() {
+    let mut world = World::new();
+    let root = world.spawn(Node).id();
+    let mut grandchild = None;
+    let parent = world
+        .spawn(Node)
+        .with_children(|parent| {
+            parent.spawn(Node).with_children(|child| {
+                grandchild = Some(child.spawn(Node).id());
+            });
+        })
+        .set_parent(root)
+        .id();
+    let sibling = world.spawn(Node).set_parent(root).id();
+
+    world.entity(parent).despawn_recursive();
+
+    assert!(!world.contains_entity(parent));
+    assert!(!world.contains_entity(grandchild.unwrap()));
+    assert_eq!(world.entity_count(), 2);
+    assert_eq!(children(&world, root), vec![sibling]);
+}
+
+#[test]
+fn despawning_a_parent_turns_its_children_into_roots() {
+    let mut world = World::new();
+    let root = world.spawn(Node).id();
+    let parent = world.spawn(Node).set_parent(root).id();
+    let child = world.spawn(Node).set_parent(parent).id();
+
+    world.despawn(parent);
+
+    assert!(world.contains_entity(child));
+    assert!(world.get_component::<Parent>(child).is_none());
+    assert!(world.get_component::<Children>(root).is_none());
+}
+
+#[test]
+fn despawn_recursive_from_commands() {
+    let mut world = World::new();
+    let parent = world
+        .spawn(Node)
+        .with_children(|parent| {
+            parent.spawn(Node);
+        })
+        .id();
+
+    world.run_system(move |mut commands: Commands| commands.entity(parent).despawn_recursive());
+
+    assert_eq!(world.entity_count(), 0);
+}
+
+#[test]
+fn children_move_with_their_parent() {
+    let mut world = world_with_propagation();
+    let mut child = None;
+    let mut grandchild = None;
+    let parent = world
+        .spawn(transform_at(10.0, 20.0))
+        .with_children(|parent| {
+            let mut spawned = parent.spawn(transform_at(1.0, 2.0));
+            spawned.with_children(|child| {
+                grandchild = Some(child.spawn(transform_at(0.5, 0.5)).id());
+            });
+            child = Some(spawned.id());
+        })
+        .id();
+    let (child, grandchild) = (child.unwrap(), grandchild.unwrap());
+
+    world.update();
+    assert_eq!(global_position(&world, parent), Vec3::new(10.0, 20.0, 0.0));
+    assert_eq!(global_position(&world, child), Vec3::new(11.0, 22.0, 0.0));
+    assert_eq!(
+        global_position(&world, grandchild),
+        Vec3::new(11.5, 22.5, 0.0)
+    );
+
+    world
+        .get_component_mut::<Transform>(parent)
+        .unwrap()
+        .position = Vec3::new(-5.0, 0.0, 1.0);
+    world.update();
+    assert_eq!(global_position(&world, child), Vec3::new(-4.0, 2.0, 1.0));
+    assert_eq!(
+        global_position(&world, grandchild),
+        Vec3::new(-3.5, 2.5, 1.0)
+    );
+}
+
+#[test]
+fn children_of_despawned_parents_keep_moving() {
+    let mut world = world_with_propagation();
+    let parent = world.spawn(transform_at(10.0, 10.0)).id();
+    let child = world.spawn(transform_at(1.0, 1.0)).set_parent(parent).id();
+    world.update();
+
+    world.despawn(parent);
+    world
+        .get_component_mut::<Transform>(child)
+        .unwrap()
+        .position = Vec3::new(5.0, 5.0, 0.0);
+    world.update();
+
+    assert_eq!(global_position(&world, child), Vec3::new(5.0, 5.0, 0.0));
+}
+
+#[test]
+fn detached_children_stop_following() {
+    let mut world = world_with_propagation();
+    let parent = world.spawn(transform_at(10.0, 10.0)).id();
+    let child = world.spawn(transform_at(1.0, 1.0)).set_parent(parent).id();
+    world.update();
+    assert_eq!(global_position(&world, child), Vec3::new(11.0, 11.0, 0.0));
+
+    world.entity(child).remove_parent();
+    world.update();
+
+    assert_eq!(global_position(&world, child), Vec3::new(1.0, 1.0, 0.0));
+}
@@ -0,0 +1,70 @@
+use oxigen::prelude::*;
+
+#[derive(Resource, Default)]
+struct Counts(Vec<u32>);
+
+fn count_runs(mut runs: Local<u32>, mut counts: ResMut<Counts>) {
+    *runs += 1;
+    counts.0.push(*runs);
+}
+
+fn counts(world: &World) -> Vec<u32> {
+    world.get_resource::<Counts>().unwrap().0.clone()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn local_is_kept_between_runs() {
+    let mut world = World::new();
+    world.init_resource::<Counts>();
+    world.add_systems(Update, count_runs);
+
+    world.update();
+    world.update();
+    world.update();
+
+    assert_eq!(counts(&world), vec![1, 2, 3]);
+}
+
+#[test]
+fn each_system_has_its_own_local() {
+    let mut world = World::new();
+    world.init_resource::<Counts>();
+    world.add_systems(Update, (count_runs, count_runs).chain());
+
+    world.update();
+    world.update();
+
+    assert_eq!(counts(&world), vec![1, 1, 2, 2]);
+}
+
+#[test]
+fn local_starts_from_the_default_in_every_run_system() {
+    let mut world = World::new();
+    world.init_resource::<Counts>();
+
+    world.run_system(count_runs);
+    world.run_system(count_runs);
+
+    assert_eq!(counts(&world), vec![1, 1]);
+}
+
+#[test]
+fn locals_work_in_conditions() {
+    let mut world = World::new();
+    world.init_resource::<Counts>();
+    let every_other_update = |mut toggle: Local<bool>| {
+        *toggle = !*toggle;
+        *toggle
+    };
+    world.add_systems(Update, count_runs.run_if(every_other_update));
+
+    for _ in 0..4 {
+        world.update();
+    }
+
+    assert_eq!(counts(&world), vec![1, 2]);
+}
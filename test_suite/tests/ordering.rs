@@ -0,0 +1,130 @@
+use oxigen::prelude::*;
+use std::sync::Mutex;
+
+/// Systems only read the log, so they don't conflict and nothing but their ordering
+/// constraints keeps them apart.
+#[derive(Resource, Default)]
+struct Log(Mutex<Vec<&'static str>>);
+
+impl Log {
+    fn push(&self, entry: &'static str) {
+        self.0.lock().unwrap().push(entry);
+    }
+
+    fn entries(world: &World) -> Vec<&'static str> {
+        world
+            .get_resource::<Log>()
+            .unwrap()
+            .0
+            .lock()
+            .unwrap()
+            .clone()
+    }
+}
+
+fn a(log: Res<Log>) {
+    log.push("a");
+}
+
+fn b(log: Res<Log>) {
+    log.push("b");
+}
+
+fn c(log: Res<Log>) {
+    log.push("c");
+}
+
+fn d(log: Res<Log>) {
+    log.push("d");
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn before_and_after_order_systems_added_separately() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_systems(Update, c.after(b));
+    world.add_systems(Update, b);
+    world.add_systems(Update, a.before(b));
+
+    world.update();
+    world.update();
+
+    assert_eq!(Log::entries(&world), vec!["a", "b", "c", "a", "b", "c"]);
+}
+
+#[test]
+fn chain_runs_systems_in_the_given_order() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_systems(Update, (c, b, a).chain());
+
+    world.update();
+
+    assert_eq!(Log::entries(&world), vec!["c", "b", "a"]);
+}
+
+#[test]
+fn chain_orders_each_instance_of_a_repeated_system() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_systems(Update, (a, b, a).chain());
+
+    world.update();
+
+    assert_eq!(Log::entries(&world), vec!["a", "b", "a"]);
+}
+
+#[test]
+fn systems_are_ordered_against_named_sets() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_systems(Update, d.after("letters"));
+    world.add_systems(Update, (a, b).in_set("letters"));
+    world.add_systems(Update, c.before("letters"));
+
+    world.update();
+
+    let log = Log::entries(&world);
+    assert_eq!(log[0], "c");
+    assert_eq!(log[3], "d");
+}
+
+#[test]
+fn systems_are_ordered_against_systems_added_earlier() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_systems(Update, (b, a, c));
+    world.add_systems(Update, d.before(a));
+
+    world.update();
+
+    let log = Log::entries(&world);
+    let position = |entry| log.iter().position(|logged| *logged == entry).unwrap();
+    assert_eq!(log.len(), 4);
+    assert!(position("d") < position("a"));
+}
+
+#[test]
+#[should_panic(expected = "ordering::a -> ordering::b -> ordering::c -> ordering::a")]
+fn cyclic_ordering_panics() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_systems(Update, (a.before(b), b.before(c), c.before(a)));
+
+    world.update();
+}
+
+#[test]
+#[should_panic(expected = "ordering::a -> ordering::b -> ordering::a")]
+fn cycle_is_found_from_a_system_after_it() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_systems(Update, c.after(a));
+    world.add_systems(Update, (a.before(b), b.before(a)));
+
+    world.update();
+}
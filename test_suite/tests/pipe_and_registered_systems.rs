@@ -0,0 +1,161 @@
+use oxigen::prelude::*;
+
+#[derive(Resource, Default)]
+struct Log(Vec<String>);
+
+#[derive(Resource)]
+struct Input(&'static str);
+
+fn parse_input(input: Res<Input>) -> Result<u32, String> {
+    input
+        .0
+        .parse()
+        .map_err(|_| format!("{:?} isn't a number", input.0))
+}
+
+fn log_parsed(In(parsed): In<Result<u32, String>>, mut log: ResMut<Log>) {
+    match parsed {
+        Ok(number) => log.0.push(format!("parsed {}", number)),
+        Err(err) => log.0.push(err),
+    }
+}
+
+fn double(input: Res<Input>) -> u32 {
+    input.0.len() as u32 * 2
+}
+
+fn fail_above_ten(In(number): In<u32>) -> Result<(), String> {
+    if number > 10 {
+        return Err(format!("{} is above ten", number));
+    }
+    Ok(())
+}
+
+fn count_runs(mut runs: Local<u32>, mut log: ResMut<Log>) {
+    *runs += 1;
+    log.0.push(format!("run {}", *runs));
+}
+
+fn log(world: &World) -> Vec<String> {
+    world.get_resource::<Log>().unwrap().0.clone()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn piped_systems_pass_their_output() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.insert_resource(Input("42"));
+    world.add_systems(Update, parse_input.pipe(log_parsed));
+
+    world.update();
+    world.insert_resource(Input("forty-two"));
+    world.update();
+
+    assert_eq!(
+        log(&world),
+        vec!["parsed 42", "\"forty-two\" isn't a number"]
+    );
+}
+
+#[test]
+fn piped_systems_can_fail() {
+    let mut world = World::new();
+    world.set_error_handler(collect_errors);
+    world.insert_resource(Input("abc"));
+
+    world.run_system(double.pipe(fail_above_ten));
+    assert!(!world.contains_resource::<Errors>());
+
+    world.insert_resource(Input("abcdef"));
+    world.run_system(double.pipe(fail_above_ten));
+    let errors = world.get_resource::<Errors>().unwrap();
+    assert_eq!(errors.0.len(), 1);
+    assert!(errors.0[0].to_string().ends_with("failed: 12 is above ten"));
+}
+
+#[test]
+fn pipes_can_be_ordered_by_label() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.insert_resource(Input("7"));
+    world.add_systems(
+        Update,
+        (
+            count_runs.after(parse_input.pipe(log_parsed)),
+            parse_input.pipe(log_parsed),
+        ),
+    );
+
+    world.update();
+
+    assert_eq!(log(&world), vec!["parsed 7", "run 1"]);
+}
+
+#[test]
+fn registered_systems_keep_their_locals() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    let id = world.register_system(count_runs);
+
+    world.run_system_by_id(id).unwrap();
+    world.run_system_by_id(id).unwrap();
+    world.run_system(count_runs);
+
+    assert_eq!(log(&world), vec!["run 1", "run 2", "run 1"]);
+}
+
+#[test]
+fn registered_systems_run_from_commands() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    let id = world.register_system(count_runs);
+    world.add_systems(Update, move |mut commands: Commands| {
+        commands.run_system(id)
+    });
+
+    world.update();
+    world.update();
+
+    assert_eq!(log(&world), vec!["run 1", "run 2"]);
+}
+
+#[test]
+fn removed_systems_cant_run() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    let id = world.register_system(count_runs);
+
+    assert!(world.remove_system(id).is_some());
+
+    assert_eq!(
+        world.run_system_by_id(id),
+        Err(RegisteredSystemError::NotRegistered(id))
+    );
+}
+
+#[test]
+fn registered_systems_cant_run_themselves() {
+    #[derive(Resource)]
+    struct Own(SystemId);
+
+    #[derive(Resource, Default)]
+    struct Results(Vec<Result<(), RegisteredSystemError>>);
+
+    let mut world = World::new();
+    world.init_resource::<Results>();
+    let id = world.register_system(|world: &mut World| {
+        let own = world.get_resource::<Own>().unwrap().0;
+        let result = world.run_system_by_id(own);
+        world.get_resource_mut::<Results>().unwrap().0.push(result);
+    });
+    world.insert_resource(Own(id));
+
+    world.run_system_by_id(id).unwrap();
+
+    let results = world.get_resource::<Results>().unwrap();
+    assert_eq!(results.0, vec![Err(RegisteredSystemError::Recursive(id))]);
+}
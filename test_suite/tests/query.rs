@@ -0,0 +1,177 @@
+use oxigen::prelude::*;
+
+#[derive(Component, Debug, PartialEq)]
+struct Position(i32);
+
+#[derive(Component, Debug, PartialEq)]
+struct Velocity(i32);
+
+#[derive(Component)]
+struct Frozen;
+
+#[test]
+fn tuple_queries_iterate_all_matching_archetypes() {
+    let mut world = World::new();
+    let moving = world.spawn((Position(0), Velocity(1))).id();
+    let frozen = world.spawn((Position(10), Velocity(5), Frozen)).id();
+    let still = world.spawn(Position(100)).id();
+
+    for (mut position, velocity) in world.query::<(&mut Position, &Velocity)>() {
+        position.0 += velocity.0;
+    }
+
+    assert_eq!(world.get_component::<Position>(moving), Some(&Position(1)));
+    assert_eq!(world.get_component::<Position>(frozen), Some(&Position(15)));
+    assert_eq!(world.get_component::<Position>(still), Some(&Position(100)));
+    assert_eq!(world.query::<&Position>().into_iter().len(), 3);
+    assert_eq!(world.query::<(Entity, &Frozen)>().into_iter().len(), 1);
+}
+
+#[test]
+fn inserting_and_removing_moves_entities_between_archetypes() {
+    let mut world = World::new();
+    let entities: Vec<Entity> = (0..3).map(|i| world.spawn(Position(i)).id()).collect();
+
+    world.entity(entities[0]).insert(Velocity(7));
+    assert_eq!(
+        world.get_component::<Position>(entities[0]),
+        Some(&Position(0))
+    );
+    assert_eq!(
+        world.get_component::<Velocity>(entities[0]),
+        Some(&Velocity(7))
+    );
+    // The entity that was swapped into the freed row is still reachable.
+    assert_eq!(
+        world.get_component::<Position>(entities[2]),
+        Some(&Position(2))
+    );
+
+    let with_velocity: Vec<Entity> = world
+        .query::<(Entity, &Velocity)>()
+        .into_iter()
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(with_velocity, vec![entities[0]]);
+
+    world.entity(entities[0]).remove::<Velocity>();
+    assert!(!world.entity(entities[0]).contains::<Velocity>());
+    assert_eq!(
+        world.get_component::<Position>(entities[0]),
+        Some(&Position(0))
+    );
+    assert_eq!(world.query::<&Velocity>().into_iter().len(), 0);
+
+    // Inserting a component the entity already has replaces it in place.
+    let location = world.entity_location(entities[1]);
+    world.entity(entities[1]).insert(Position(42));
+    assert_eq!(world.entity_location(entities[1]), location);
+    assert_eq!(
+        world.get_component::<Position>(entities[1]),
+        Some(&Position(42))
+    );
+}
+
+#[derive(Component, Debug, PartialEq)]
+#[component(storage = "SparseSet")]
+struct Selected(u8);
+
+#[test]
+fn sparse_set_components_mix_with_table_components() {
+    let mut world = World::new();
+    let a = world.spawn((Position(1), Selected(1))).id();
+    let b = world.spawn(Position(2)).id();
+    let c = world.spawn((Position(3), Selected(3))).id();
+
+    for (mut position, selected) in world.query::<(&mut Position, &Selected)>() {
+        position.0 += selected.0 as i32;
+    }
+    assert_eq!(world.get_component::<Position>(a), Some(&Position(2)));
+    assert_eq!(world.get_component::<Position>(b), Some(&Position(2)));
+    assert_eq!(world.get_component::<Position>(c), Some(&Position(6)));
+
+    // Toggling a sparse-set component doesn't move the table components of the entity.
+    let table_location = world.entity_location(b).unwrap();
+    world.entity(b).insert(Selected(2));
+    assert_eq!(world.get_component::<Selected>(b), Some(&Selected(2)));
+    let location = world.entity_location(b).unwrap();
+    assert_ne!(location.archetype_id, table_location.archetype_id);
+    assert_eq!(location.table_id, table_location.table_id);
+    assert_eq!(location.table_row, table_location.table_row);
+
+    assert_eq!(world.entity(a).take::<Selected>(), Some(Selected(1)));
+    world.entity(c).remove::<Selected>();
+    let selected: Vec<Entity> = world
+        .query::<(Entity, &Selected)>()
+        .into_iter()
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(selected, vec![b]);
+
+    world.despawn(b);
+    assert_eq!(world.query::<&Selected>().into_iter().len(), 0);
+    assert_eq!(world.get_component::<Position>(c), Some(&Position(6)));
+}
+
+#[derive(Component)]
+struct Enemy;
+
+#[derive(Component)]
+struct Dead;
+
+#[test]
+fn filters_narrow_down_matched_entities() {
+    let mut world = World::new();
+    let alive_enemy = world.spawn((Enemy, Position(0))).id();
+    let dead_enemy = world.spawn((Enemy, Dead, Position(1))).id();
+    let frozen = world.spawn((Frozen, Position(2))).id();
+    world.spawn(Position(3));
+
+    let alive_enemies = world.query_filtered::<Entity, (With<Enemy>, Without<Dead>)>();
+    assert!(alive_enemies.get(dead_enemy).is_err());
+    assert_eq!(
+        alive_enemies.into_iter().collect::<Vec<_>>(),
+        vec![alive_enemy]
+    );
+
+    let mut enemies_or_frozen = world
+        .query_filtered::<Entity, Or<(With<Enemy>, With<Frozen>)>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    enemies_or_frozen.sort();
+    assert_eq!(enemies_or_frozen, vec![alive_enemy, dead_enemy, frozen]);
+}
+
+#[test]
+fn optional_components_are_fetched_when_present() {
+    let mut world = World::new();
+    let with_velocity = world.spawn((Position(0), Velocity(3))).id();
+    let without_velocity = world.spawn(Position(10)).id();
+
+    for (mut position, velocity) in world.query::<(&mut Position, Option<&Velocity>)>() {
+        position.0 += velocity.map_or(-1, |velocity| velocity.0);
+    }
+    assert_eq!(
+        world.get_component::<Position>(with_velocity),
+        Some(&Position(3))
+    );
+    assert_eq!(
+        world.get_component::<Position>(without_velocity),
+        Some(&Position(9))
+    );
+
+    for mut velocity in world.query::<Option<&mut Velocity>>().into_iter().flatten() {
+        velocity.0 *= 2;
+    }
+    assert_eq!(
+        world.get_component::<Velocity>(with_velocity),
+        Some(&Velocity(6))
+    );
+    assert_eq!(
+        world
+            .query::<Option<&Velocity>>()
+            .get(without_velocity)
+            .unwrap(),
+        None
+    );
+}
@@ -0,0 +1,130 @@
+use oxigen::prelude::*;
+
+#[derive(Component, Debug, PartialEq)]
+struct Position(i32);
+
+#[derive(Component)]
+struct Velocity(i32);
+
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+struct Tag;
+
+#[derive(Resource, Default)]
+struct Seen(Vec<usize>);
+
+fn count_positions(query: Query<&Position>, mut seen: ResMut<Seen>) {
+    seen.0.push(query.into_iter().count());
+}
+
+fn spawn_moving(mut commands: Commands) {
+    commands.spawn((Position(0), Velocity(1)));
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn query_state_matches_archetypes_created_later() {
+    let mut world = World::new();
+    world.spawn(Position(1));
+    let mut state = world.query_state::<&Position>();
+    assert_eq!(state.query(&world).into_iter().count(), 1);
+
+    world.spawn((Position(2), Velocity(0)));
+    world.spawn(Velocity(0));
+    world.spawn((Position(3), Tag));
+
+    let mut positions = state
+        .query(&world)
+        .into_iter()
+        .map(|position| position.0)
+        .collect::<Vec<_>>();
+    positions.sort();
+    assert_eq!(positions, vec![1, 2, 3]);
+}
+
+#[test]
+fn query_state_only_checks_new_archetypes() {
+    let mut world = World::new();
+    world.spawn(Position(1));
+    let mut state = world.query_state::<&Position>();
+    let matched = state.matched_archetypes().len();
+
+    state.update_archetypes(&world);
+    assert_eq!(state.matched_archetypes().len(), matched);
+
+    world.spawn((Position(2), Velocity(0)));
+    state.update_archetypes(&world);
+    assert_eq!(state.matched_archetypes().len(), matched + 1);
+}
+
+#[test]
+fn filtered_query_state() {
+    let mut world = World::new();
+    world.spawn(Position(1));
+    world.spawn((Position(2), Velocity(0)));
+    let mut state = world.query_state_filtered::<&Position, Without<Velocity>>();
+
+    let positions = state.query(&world).into_iter().collect::<Vec<_>>();
+    assert_eq!(positions, vec![&Position(1)]);
+}
+
+#[test]
+fn query_state_gets_single_entities() {
+    let mut world = World::new();
+    let still = world.spawn(Position(1)).id();
+    let moving = world.spawn((Position(2), Velocity(3))).id();
+    let mut state = world.query_state::<(&Position, &Velocity)>();
+
+    assert!(state.get(&world, still).is_err());
+    let (position, velocity) = state.get(&world, moving).unwrap();
+    assert_eq!((position.0, velocity.0), (2, 3));
+}
+
+#[test]
+fn mutable_query_states_borrow_the_world_mutably() {
+    let mut world = World::new();
+    let entity = world.spawn(Position(1)).id();
+    world.spawn((Position(2), Velocity(0)));
+    let mut state = world.query_state::<&mut Position>();
+
+    for mut position in state.query_mut(&mut world) {
+        position.0 *= 10;
+    }
+    state.get_mut(&mut world, entity).unwrap().0 += 1;
+
+    let mut positions = world
+        .query_state::<&Position>()
+        .query(&world)
+        .into_iter()
+        .map(|position| position.0)
+        .collect::<Vec<_>>();
+    positions.sort();
+    assert_eq!(positions, vec![11, 20]);
+}
+
+#[test]
+#[should_panic(expected = "was created for another World")]
+fn query_states_cant_be_used_with_another_world() {
+    let world = World::new();
+    let other = World::new();
+    let mut state = world.query_state::<&Position>();
+
+    state.query(&other);
+}
+
+#[test]
+fn system_queries_see_archetypes_created_between_runs() {
+    let mut world = World::new();
+    world.init_resource::<Seen>();
+    world.spawn(Position(0));
+    world.add_systems(Update, (count_positions, spawn_moving).chain());
+
+    world.update();
+    world.update();
+    world.update();
+
+    assert_eq!(world.get_resource::<Seen>().unwrap().0, vec![1, 2, 3]);
+}
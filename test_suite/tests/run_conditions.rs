@@ -0,0 +1,177 @@
+use oxigen::prelude::*;
+use std::sync::Mutex;
+
+#[derive(Resource, Default)]
+struct Counter(u32);
+
+#[derive(Resource)]
+struct Config {
+    enabled: bool,
+}
+
+struct Ping;
+
+fn count(mut counter: ResMut<Counter>) {
+    counter.0 += 1;
+}
+
+fn count_with_config(config: Res<Config>, mut counter: ResMut<Counter>) {
+    if config.enabled {
+        counter.0 += 1;
+    }
+}
+
+fn ping(mut writer: EventWriter<Ping>) {
+    writer.send(Ping);
+}
+
+fn is_enabled(config: Res<Config>) -> bool {
+    config.enabled
+}
+
+fn always(_counter: Res<Counter>) -> bool {
+    true
+}
+
+fn write_counter(mut counter: ResMut<Counter>) -> bool {
+    counter.0 += 1;
+    true
+}
+
+fn counter(world: &World) -> u32 {
+    world.get_resource::<Counter>().unwrap().0
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn missing_resource_skips_the_system() {
+    let mut world = World::new();
+    world.init_resource::<Counter>();
+    world.add_systems(Update, count_with_config.run_if(resource_exists::<Config>));
+
+    world.update();
+    assert_eq!(counter(&world), 0);
+
+    world.insert_resource(Config { enabled: true });
+    world.update();
+    assert_eq!(counter(&world), 1);
+}
+
+#[test]
+fn system_runs_only_while_the_condition_is_true() {
+    let mut world = World::new();
+    world.init_resource::<Counter>();
+    world.insert_resource(Config { enabled: false });
+    world.add_systems(Update, count.run_if(is_enabled));
+
+    world.update();
+    assert_eq!(counter(&world), 0);
+
+    world.get_resource_mut::<Config>().unwrap().enabled = true;
+    world.update();
+    world.update();
+    assert_eq!(counter(&world), 2);
+}
+
+#[test]
+fn every_condition_has_to_be_true() {
+    let mut world = World::new();
+    world.init_resource::<Counter>();
+    world.insert_resource(Config { enabled: false });
+    world.add_systems(Update, count.run_if(always).run_if(is_enabled));
+
+    world.update();
+    assert_eq!(counter(&world), 0);
+}
+
+#[test]
+fn closures_are_conditions() {
+    let mut world = World::new();
+    world.init_resource::<Counter>();
+    world.add_systems(Update, count.run_if(|counter: Res<Counter>| counter.0 < 3));
+
+    for _ in 0..5 {
+        world.update();
+    }
+    assert_eq!(counter(&world), 3);
+}
+
+#[test]
+fn condition_applies_to_every_system_of_a_set() {
+    let mut world = World::new();
+    world.init_resource::<Counter>();
+    world.add_systems(Update, (count, count).run_if(resource_exists::<Config>));
+
+    world.update();
+    assert_eq!(counter(&world), 0);
+
+    world.insert_resource(Config { enabled: true });
+    world.update();
+    assert_eq!(counter(&world), 2);
+}
+
+#[test]
+fn on_event_runs_the_system_once_per_sent_batch() {
+    let mut world = World::new();
+    world.init_resource::<Counter>();
+    world.add_event::<Ping>();
+    world.add_systems(Update, count.run_if(on_event::<Ping>));
+
+    world.update();
+    assert_eq!(counter(&world), 0);
+
+    world.run_system(ping);
+    world.update();
+    world.update();
+    assert_eq!(counter(&world), 1);
+}
+
+#[test]
+#[should_panic(expected = "can only read the world")]
+fn conditions_cannot_write() {
+    let mut world = World::new();
+    world.add_systems(Update, count.run_if(write_counter));
+}
+
+#[test]
+fn schedule_condition_gates_the_whole_schedule() {
+    let mut world = World::new();
+    world.init_resource::<Counter>();
+    world.insert_resource(Config { enabled: false });
+    world.add_systems(Update, (count, count));
+    world.add_schedule_condition(Update, is_enabled);
+
+    world.update();
+    assert_eq!(counter(&world), 0);
+
+    world.get_resource_mut::<Config>().unwrap().enabled = true;
+    world.update();
+    assert_eq!(counter(&world), 2);
+}
+
+#[test]
+fn schedule_conditions_are_evaluated_once_per_run() {
+    #[derive(Resource, Default)]
+    struct Evaluations(Mutex<u32>);
+
+    fn evaluated(evaluations: Res<Evaluations>) -> bool {
+        *evaluations.0.lock().unwrap() += 1;
+        true
+    }
+
+    let mut world = World::new();
+    world.init_resource::<Counter>();
+    world.init_resource::<Evaluations>();
+    world.add_systems(Update, (count, count));
+    world.add_schedule_condition(Update, evaluated);
+
+    world.update();
+    world.update();
+
+    assert_eq!(counter(&world), 4);
+    let evaluations = world.get_resource::<Evaluations>().unwrap();
+    assert_eq!(*evaluations.0.lock().unwrap(), 2);
+}
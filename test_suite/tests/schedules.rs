@@ -0,0 +1,138 @@
+use oxigen::prelude::*;
+
+#[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash, Debug)]
+enum Stage {
+    Physics,
+    Audio,
+}
+
+#[derive(ScheduleLabel, Clone, PartialEq, Eq, Hash, Debug)]
+struct Loading;
+
+#[derive(Resource, Default)]
+struct Log(Vec<&'static str>);
+
+fn log(entry: &'static str) -> impl FnMut(ResMut<Log>) + Send + Sync {
+    move |mut log: ResMut<Log>| log.0.push(entry)
+}
+
+fn entries(world: &World) -> Vec<&'static str> {
+    world.get_resource::<Log>().unwrap().0.clone()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn enum_variants_are_different_schedules() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_systems(Stage::Physics, log("physics"));
+    world.add_systems(Stage::Audio, log("audio"));
+
+    world.run_schedule(Stage::Audio);
+    world.run_schedule(Stage::Physics);
+    world.run_schedule(Stage::Audio);
+
+    assert_eq!(entries(&world), vec!["audio", "physics", "audio"]);
+}
+
+#[test]
+fn main_schedules_run_in_order() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_systems(PostUpdate, log("post update"));
+    world.add_systems(Update, log("update"));
+    world.add_systems(PreUpdate, log("pre update"));
+    world.add_systems(Startup, log("startup"));
+
+    world.update();
+    world.update();
+
+    assert_eq!(
+        entries(&world),
+        vec![
+            "startup",
+            "pre update",
+            "update",
+            "post update",
+            "pre update",
+            "update",
+            "post update"
+        ]
+    );
+}
+
+#[test]
+fn inserted_schedules_run_at_their_place() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.schedules_mut().insert_after(Update, Stage::Physics);
+    world.schedules_mut().insert_before(Update, Stage::Audio);
+    world.schedules_mut().insert_startup_after(Startup, Loading);
+    world.add_systems(Update, log("update"));
+    world.add_systems(Stage::Physics, log("physics"));
+    world.add_systems(Stage::Audio, log("audio"));
+    world.add_systems(Loading, log("loading"));
+    world.add_systems(Startup, log("startup"));
+
+    world.update();
+
+    assert_eq!(
+        entries(&world),
+        vec!["startup", "loading", "audio", "update", "physics"]
+    );
+}
+
+#[test]
+fn schedules_outside_of_the_order_only_run_explicitly() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_systems(Loading, log("loading"));
+
+    world.update();
+    assert!(entries(&world).is_empty());
+
+    world.run_schedule(Loading);
+    assert_eq!(entries(&world), vec!["loading"]);
+}
+
+#[test]
+fn startup_schedules_only_run_once() {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.add_systems(Startup, log("startup"));
+
+    world.run_startup_schedules();
+    world.update();
+
+    assert_eq!(entries(&world), vec!["startup"]);
+}
+
+#[test]
+fn try_running_an_unconfigured_schedule_fails() {
+    let mut world = World::new();
+
+    assert!(world.try_run_schedule(Loading).is_err());
+
+    world.init_schedule(Loading);
+    assert!(world.try_run_schedule(Loading).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "The schedule Physics was run but never configured")]
+fn running_an_unconfigured_schedule_panics() {
+    let mut world = World::new();
+    world.add_systems(Stage::Audio, log("audio"));
+
+    world.run_schedule(Stage::Physics);
+}
+
+#[test]
+#[should_panic(expected = "The schedule Update is already in the schedule order")]
+fn main_schedules_cannot_be_inserted_twice() {
+    let mut world = World::new();
+
+    world.schedules_mut().insert_after(PostUpdate, Update);
+}
@@ -1,5 +1,5 @@
 use oxigen::prelude::*;
-use std::time::Instant;
+use std::time::Duration;
 
 #[derive(Resource)]
 struct Counter(usize);
@@ -8,7 +8,7 @@ struct Counter(usize);
 struct MyComponent;
 
 #[derive(Resource)]
-struct Timer(Instant);
+struct Timer(Duration);
 
 /// Must have the [`Counter`] resource in the world.
 fn stop_when_counter_is<const N: usize>(world: &World) -> bool {
@@ -16,10 +16,14 @@ fn stop_when_counter_is<const N: usize>(world: &World) -> bool {
     counter.0 >= N
 }
 
-fn increment_counter_every_n_secs(mut counter: ResMut<Counter>, mut timer: ResMut<Timer>) {
-    if timer.0.elapsed().as_secs() >= 1 as u64 {
+fn increment_counter_every_n_secs(
+    mut counter: ResMut<Counter>,
+    mut timer: ResMut<Timer>,
+    time: Res<Time>,
+) {
+    if (time.elapsed() - timer.0).as_secs() >= 1 as u64 {
         counter.0 += 1;
-        timer.0 = std::time::Instant::now();
+        timer.0 = time.elapsed();
     }
 }
 
@@ -31,9 +35,13 @@ fn increment_counter_every_n_secs(mut counter: ResMut<Counter>, mut timer: ResMu
 fn counter_test() {
     const N: usize = 2;
     let mut app = App::new().with_stop_condition(stop_when_counter_is::<N>);
+    app.add_plugin(TimePlugin);
     app.add_systems(Update, increment_counter_every_n_secs);
     app.insert_resource(Counter(0))
-        .insert_resource(Timer(Instant::now()));
+        .insert_resource(Timer(Duration::ZERO))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            250,
+        )));
 
     let world_after_run = app.run();
 
@@ -0,0 +1,146 @@
+use oxigen::prelude::*;
+
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+enum GameState {
+    #[default]
+    Menu,
+    Playing,
+    Paused,
+}
+
+#[derive(Resource, Default)]
+struct Log(Vec<&'static str>);
+
+#[derive(Resource, Default)]
+struct Frames(u32);
+
+fn log(entry: &'static str) -> impl FnMut(ResMut<Log>) + Send + Sync {
+    move |mut log: ResMut<Log>| log.0.push(entry)
+}
+
+fn count_frames(mut frames: ResMut<Frames>) {
+    frames.0 += 1;
+}
+
+fn start_playing(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Playing);
+}
+
+fn setup_world() -> World {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world.init_resource::<Frames>();
+    world.add_state::<GameState>();
+    world
+}
+
+fn set_state(world: &mut World, state: GameState) {
+    world
+        .get_resource_mut::<NextState<GameState>>()
+        .unwrap()
+        .set(state);
+}
+
+fn current_state(world: &World) -> GameState {
+    *world.get_resource::<State<GameState>>().unwrap().get()
+}
+
+fn entries(world: &World) -> Vec<&'static str> {
+    world.get_resource::<Log>().unwrap().0.clone()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn starts_in_the_default_state_and_enters_it_on_the_first_update() {
+    let mut world = setup_world();
+    world.add_systems(OnEnter(GameState::Menu), log("enter menu"));
+
+    assert_eq!(current_state(&world), GameState::Menu);
+    world.update();
+    world.update();
+
+    assert_eq!(entries(&world), vec!["enter menu"]);
+}
+
+#[test]
+fn transitions_run_exit_transition_and_enter_schedules_in_order() {
+    let mut world = setup_world();
+    world.add_systems(OnExit(GameState::Menu), log("exit menu"));
+    world.add_systems(
+        OnTransition {
+            from: GameState::Menu,
+            to: GameState::Playing,
+        },
+        log("menu to playing"),
+    );
+    world.add_systems(OnEnter(GameState::Playing), log("enter playing"));
+    world.add_systems(OnEnter(GameState::Paused), log("enter paused"));
+    world.update();
+
+    set_state(&mut world, GameState::Playing);
+    world.update();
+
+    assert_eq!(current_state(&world), GameState::Playing);
+    assert_eq!(
+        entries(&world),
+        vec!["exit menu", "menu to playing", "enter playing"]
+    );
+}
+
+#[test]
+fn setting_the_current_state_does_nothing() {
+    let mut world = setup_world();
+    world.add_systems(OnExit(GameState::Menu), log("exit menu"));
+    world.update();
+
+    set_state(&mut world, GameState::Menu);
+    world.update();
+
+    assert!(entries(&world).is_empty());
+}
+
+#[test]
+fn transition_is_applied_before_update() {
+    let mut world = setup_world();
+    world.add_systems(PreUpdate, start_playing);
+    world.add_systems(Update, count_frames.run_if(in_state(GameState::Playing)));
+
+    world.update();
+
+    assert_eq!(current_state(&world), GameState::Playing);
+    assert_eq!(world.get_resource::<Frames>().unwrap().0, 1);
+}
+
+#[test]
+fn in_state_only_runs_systems_in_the_state() {
+    let mut world = setup_world();
+    world.add_systems(Update, count_frames.run_if(in_state(GameState::Playing)));
+
+    world.update();
+    set_state(&mut world, GameState::Playing);
+    world.update();
+    world.update();
+    set_state(&mut world, GameState::Paused);
+    world.update();
+
+    assert_eq!(world.get_resource::<Frames>().unwrap().0, 2);
+}
+
+#[test]
+fn app_adds_states_and_state_systems() {
+    let mut app = App::new();
+    app.init_resource::<Log>()
+        .add_state::<GameState>()
+        .add_systems(OnEnter(GameState::Menu), log("enter menu"))
+        .add_systems(Update, start_playing)
+        .add_systems(OnEnter(GameState::Playing), log("enter playing"));
+
+    let world = app.world();
+    world.update();
+    world.update();
+
+    assert_eq!(entries(world), vec!["enter menu", "enter playing"]);
+}
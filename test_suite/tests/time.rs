@@ -0,0 +1,125 @@
+use oxigen::prelude::*;
+use std::time::Duration;
+
+#[derive(Resource, Default)]
+struct FixedSteps(Vec<Duration>);
+
+fn record_fixed_step(time: Res<Time>, mut steps: ResMut<FixedSteps>) {
+    steps.0.push(time.elapsed());
+}
+
+fn world_with_manual_clock(delta: Duration) -> World {
+    let mut world = World::new();
+    world.init_time();
+    world.insert_resource(TimeUpdateStrategy::ManualDuration(delta));
+    world.insert_resource(Time::<Fixed>::from_duration(Duration::from_millis(20)));
+    world.init_resource::<FixedSteps>();
+    world.add_systems(FixedUpdate, record_fixed_step);
+    world
+}
+
+fn time(world: &World) -> Time {
+    *world.get_resource::<Time>().unwrap()
+}
+
+fn fixed_steps(world: &World) -> Vec<Duration> {
+    world.get_resource::<FixedSteps>().unwrap().0.clone()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn manual_clock_advances_by_the_same_delta_each_update() {
+    let mut world = world_with_manual_clock(Duration::from_millis(10));
+
+    for _ in 0..3 {
+        world.update();
+    }
+
+    let time = time(&world);
+    assert_eq!(time.delta(), Duration::from_millis(10));
+    assert_eq!(time.elapsed(), Duration::from_millis(30));
+    assert_eq!(time.frame_count(), 3);
+}
+
+#[test]
+fn fixed_update_runs_once_per_accumulated_step() {
+    let mut world = world_with_manual_clock(Duration::from_millis(30));
+
+    world.update();
+    assert_eq!(fixed_steps(&world), vec![Duration::from_millis(20)]);
+    let fixed = *world.get_resource::<Time<Fixed>>().unwrap();
+    assert_eq!(fixed.overstep(), Duration::from_millis(10));
+
+    world.update();
+    assert_eq!(
+        fixed_steps(&world),
+        vec![
+            Duration::from_millis(20),
+            Duration::from_millis(40),
+            Duration::from_millis(60)
+        ]
+    );
+    // After the fixed steps, Time is the virtual clock again.
+    assert_eq!(time(&world).elapsed(), Duration::from_millis(60));
+}
+
+#[test]
+fn fixed_update_can_run_zero_times() {
+    let mut world = world_with_manual_clock(Duration::from_millis(5));
+
+    for _ in 0..3 {
+        world.update();
+    }
+    assert!(fixed_steps(&world).is_empty());
+
+    world.update();
+    assert_eq!(fixed_steps(&world).len(), 1);
+}
+
+#[test]
+fn paused_clock_doesnt_advance() {
+    let mut world = world_with_manual_clock(Duration::from_millis(20));
+    world.update();
+
+    world.get_resource_mut::<Time<Virtual>>().unwrap().pause();
+    world.update();
+    world.update();
+
+    assert_eq!(time(&world).elapsed(), Duration::from_millis(20));
+    assert_eq!(fixed_steps(&world).len(), 1);
+    let real = *world.get_resource::<Time<Real>>().unwrap();
+    assert_eq!(real.elapsed(), Duration::from_millis(60));
+}
+
+#[test]
+fn relative_speed_scales_the_clock() {
+    let mut world = world_with_manual_clock(Duration::from_millis(10));
+    world
+        .get_resource_mut::<Time<Virtual>>()
+        .unwrap()
+        .set_relative_speed(2.0);
+
+    world.update();
+    world.update();
+
+    assert_eq!(time(&world).delta(), Duration::from_millis(20));
+    assert_eq!(time(&world).elapsed(), Duration::from_millis(40));
+    assert_eq!(fixed_steps(&world).len(), 2);
+}
+
+#[test]
+fn long_updates_are_capped() {
+    let mut world = world_with_manual_clock(Duration::from_secs(10));
+    world
+        .get_resource_mut::<Time<Virtual>>()
+        .unwrap()
+        .set_max_delta(Duration::from_millis(100));
+
+    world.update();
+
+    assert_eq!(time(&world).delta(), Duration::from_millis(100));
+    assert_eq!(fixed_steps(&world).len(), 5);
+}
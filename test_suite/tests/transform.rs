@@ -0,0 +1,97 @@
+use std::f32::consts::FRAC_PI_2;
+
+use oxigen::prelude::*;
+
+fn assert_close(a: Vec2, b: Vec2) {
+    assert!(a.abs_diff_eq(b, 1e-4), "{} != {}", a, b);
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//                                 TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[test]
+fn default_transform_is_the_identity() {
+    let transform = Transform::default();
+    assert_eq!(transform.scale, Vec2::ONE);
+    assert_eq!(transform.compute_matrix(), Mat3::IDENTITY);
+}
+
+#[test]
+fn matrix_scales_then_rotates_then_translates() {
+    let transform = Transform::from_position(Vec3::new(10.0, 20.0, 3.0))
+        .with_rotation(FRAC_PI_2)
+        .with_scale(Vec2::new(2.0, 3.0));
+
+    let matrix = transform.compute_matrix();
+    // (1, 0) is scaled to (2, 0), rotated to (0, 2) and moved to (10, 22).
+    assert_close(
+        matrix.transform_point2(Vec2::new(1.0, 0.0)),
+        Vec2::new(10.0, 22.0),
+    );
+    // (0, 1) is scaled to (0, 3), rotated to (-3, 0) and moved to (7, 20).
+    assert_close(
+        matrix.transform_point2(Vec2::new(0.0, 1.0)),
+        Vec2::new(7.0, 20.0),
+    );
+    assert_eq!(Mat3::from(transform.compute_affine()), matrix);
+}
+
+#[test]
+fn mul_transform_composes_transforms() {
+    let parent = Transform::from_position(Vec3::new(100.0, 0.0, 1.0))
+        .with_rotation(FRAC_PI_2)
+        .with_scale(Vec2::splat(2.0));
+    let child = Transform::from_position(Vec3::new(5.0, 0.0, 2.0)).with_rotation(FRAC_PI_2);
+
+    let composed = parent.mul_transform(child);
+    assert_close(composed.position.truncate(), Vec2::new(100.0, 10.0));
+    assert_eq!(composed.position.z, 3.0);
+    assert_eq!(composed.rotation, 2.0 * FRAC_PI_2);
+    assert_eq!(composed.scale, Vec2::splat(2.0));
+
+    let point = Vec2::new(1.0, 1.0);
+    assert_close(
+        composed.compute_matrix().transform_point2(point),
+        parent
+            .compute_matrix()
+            .transform_point2(child.compute_matrix().transform_point2(point)),
+    );
+}
+
+#[test]
+fn global_transforms_compose_non_uniform_scales_exactly() {
+    let parent = Transform::default().with_scale(Vec2::new(1.0, 4.0));
+    let child = Transform::from_position(Vec3::new(1.0, 1.0, 0.0)).with_rotation(FRAC_PI_2);
+
+    let global = GlobalTransform::from(parent).mul_transform(child);
+
+    let point = Vec2::new(1.0, 0.0);
+    let expected = parent
+        .compute_matrix()
+        .transform_point2(child.compute_matrix().transform_point2(point));
+    assert_close(global.transform_point(point), expected);
+    assert_close(global.compute_matrix().transform_point2(point), expected);
+}
+
+#[test]
+fn rotated_parents_rotate_their_children() {
+    let mut world = World::new();
+    world.add_systems(PostUpdate, propagate_transforms);
+    let parent_transform =
+        Transform::from_position(Vec3::new(50.0, 50.0, 0.0)).with_rotation(FRAC_PI_2);
+    let parent = world
+        .spawn((parent_transform, GlobalTransform::default()))
+        .id();
+    let child_transform = Transform::from_position(Vec3::new(10.0, 0.0, 1.0));
+    let child = world
+        .spawn((child_transform, GlobalTransform::default()))
+        .set_parent(parent)
+        .id();
+
+    world.update();
+
+    let global = *world.get_component::<GlobalTransform>(child).unwrap();
+    assert_close(global.position().truncate(), Vec2::new(50.0, 60.0));
+    assert_eq!(global.position().z, 1.0);
+}